@@ -4,7 +4,7 @@ use ink_lang as ink;
 
 #[ink::contract]
 mod ink_voting_dapp {
-    use ink_prelude::vec::Vec;
+    use ink_prelude::{vec, vec::Vec};
     use ink_storage::{
         traits::PackedLayout, traits::SpreadAllocate, traits::SpreadLayout, Mapping,
     };
@@ -22,8 +22,40 @@ mod ink_voting_dapp {
         proposals_ids: Mapping<(u32, Vec<u8>), u32>,
         proposals_list: Mapping<u32, Vec<Vec<u8>>>,
         voters: Mapping<(u32, AccountId), (u128, bool)>,
+        voter_choices: Mapping<(u32, AccountId), VoterChoices>,
+        election_configs: Mapping<u32, ElectionConfig>,
+        motions: Mapping<u32, bool>,
+        proposals_locked: Mapping<u32, bool>,
+        created_at: Mapping<u32, Timestamp>,
+        owner_elections: Mapping<AccountId, Vec<u32>>,
+        finalized_results: Mapping<u32, (Vec<u8>, u128)>,
+        voter_count: Mapping<u32, u32>,
+        voted_count: Mapping<u32, u32>,
+        first_vote_done: Mapping<u32, bool>,
+        proposal_caps: Mapping<(u32, u32), u128>,
+        voter_list: Mapping<u32, Vec<AccountId>>,
+        eligible_weight: Mapping<u32, u128>,
+        templates: Mapping<(AccountId, Vec<u8>), Vec<Vec<u8>>>,
+        delegated_in: Mapping<(u32, AccountId), u128>,
+        outcomes: Mapping<u32, ElectionOutcome>,
+        pending_owners: Mapping<u32, AccountId>,
+        opened_at: Mapping<u32, Timestamp>,
+        authorization_nonces: Mapping<AccountId, u64>,
+        current_round: Mapping<u32, u32>,
+        round_results: Mapping<(u32, u32), ProposalResults>,
+        proposal_first_vote_ts: Mapping<(u32, u32), Timestamp>,
+        voter_elections: Mapping<AccountId, Vec<u32>>,
+        public_templates: Mapping<Vec<u8>, Vec<Vec<u8>>>,
+        proposal_order: Mapping<u32, Vec<u32>>,
+        elections_metadata: Mapping<u32, Vec<u8>>,
+        force_closed: Mapping<u32, bool>,
         election_nonce: u32,
         election_count: u32,
+        admin: AccountId,
+        paused: bool,
+        storage_version: u16,
+        max_elections_per_account: u32,
+        max_vote_weight: u128,
     }
 
     #[ink(event)]
@@ -32,6 +64,7 @@ mod ink_voting_dapp {
         id: u32,
         owner: AccountId,
         require_registration: bool,
+        proposal_count: u32,
     }
 
     #[ink(event)]
@@ -39,6 +72,30 @@ mod ink_voting_dapp {
         voter: AccountId,
         proposal: Vec<u8>,
         weight: u128,
+        new_total: u128,
+    }
+
+    /// Emitted instead of `Voted` in anonymized elections. `voter_hash` is
+    /// `blake2x256(voter ++ salt)`.
+    #[ink(event)]
+    pub struct AnonymousVoted {
+        election_id: u32,
+        voter_hash: [u8; 32],
+        proposal: Vec<u8>,
+        weight: u128,
+        new_total: u128,
+    }
+
+    #[ink(event)]
+    pub struct VotingStarted {
+        election_id: u32,
+        date: Timestamp,
+    }
+
+    #[ink(event)]
+    pub struct WriteInAdded {
+        election_id: u32,
+        proposal: Vec<u8>,
     }
 
     #[ink(event)]
@@ -47,6 +104,19 @@ mod ink_voting_dapp {
         election_id: u32,
     }
 
+    #[ink(event)]
+    pub struct BatchRegistered {
+        election_id: u32,
+        count: u32,
+    }
+
+    #[ink(event)]
+    pub struct WeightAdjusted {
+        election_id: u32,
+        voter: AccountId,
+        weight: u128,
+    }
+
     #[ink(event)]
     pub struct OpenRegistration {
         election_id: u32,
@@ -71,12 +141,25 @@ mod ink_voting_dapp {
         date: Timestamp,
     }
 
+    #[ink(event)]
+    pub struct FinalizeElection {
+        election_id: u32,
+        winner: Vec<u8>,
+        votes: u128,
+    }
+
     #[ink(event)]
     pub struct ChangeOwnership {
         election_id: u32,
         new_owner: AccountId,
     }
 
+    #[ink(event)]
+    pub struct OwnershipProposed {
+        election_id: u32,
+        candidate: AccountId,
+    }
+
     #[ink(event)]
     pub struct Delegate {
         election_id: u32,
@@ -84,6 +167,59 @@ mod ink_voting_dapp {
         delegator: AccountId,
     }
 
+    #[ink(event)]
+    pub struct VoteRevoked {
+        election_id: u32,
+        voter: AccountId,
+        weight: u128,
+    }
+
+    /// Emitted instead of `VoteRevoked` in anonymized elections, with the
+    /// same `voter_hash` as the voter's `AnonymousVoted` events.
+    #[ink(event)]
+    pub struct AnonymousVoteRevoked {
+        election_id: u32,
+        voter_hash: [u8; 32],
+        weight: u128,
+    }
+
+    #[ink(event)]
+    pub struct AdminForceClosed {
+        election_id: u32,
+        admin: AccountId,
+    }
+
+    #[ink(event)]
+    pub struct RoundClosed {
+        election_id: u32,
+        round: u32,
+    }
+
+    #[ink(event)]
+    pub struct Archived {
+        election_id: u32,
+    }
+
+    #[ink(event)]
+    pub struct ResultChunk {
+        election_id: u32,
+        start: u32,
+        results: ProposalResults,
+    }
+
+    #[ink(event)]
+    pub struct MetadataUpdated {
+        election_id: u32,
+        metadata: Vec<u8>,
+    }
+
+    #[ink(event)]
+    pub struct PublishedTemplate {
+        election_id: u32,
+        name: Vec<u8>,
+        publisher: AccountId,
+    }
+
     #[derive(Debug, PartialEq, Eq, scale::Encode, scale::Decode)]
     #[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
     pub enum Error {
@@ -97,40 +233,228 @@ mod ink_voting_dapp {
         ElectionClosed,
         RegistrationClosed,
         VoterAlreadyRegistered,
+        HasNotVoted,
+        InvalidProof,
+        ProposalsLocked,
+        OnlyAdmin,
+        WeightOverflow,
+        ElectionFinalized,
+        ElectionStillOpen,
+        ContractPaused,
+        InvalidCaps,
+        ProposalCapReached,
+        TooManyProposals,
+        TallyOverflow,
+        TemplateNotFound,
+        WeightExceeded,
+        DelegatorNotRegistered,
+        DelegateNotRegistered,
+        VoterLimitReached,
+        NotPendingOwner,
+        SignatureInvalid,
+        VotingNotStarted,
+        WeightMustMatchFull,
+        ElectionNotFinalized,
+        VersionMismatch,
+        CreationLimitReached,
+        ResultsHidden,
+        SameOwner,
+        TemplateExists,
+        WeightTooLarge,
+        InvalidOrder,
+        ProofRequired,
+        SplitNotAllowed,
+        ElectionAlreadyOpened,
+        SelfDelegation,
+        ElectionForceClosed,
     }
-    #[derive(SpreadLayout, PackedLayout, Debug, PartialEq, Eq, scale::Encode, scale::Decode)]
+    #[derive(
+        SpreadLayout, PackedLayout, Debug, Default, PartialEq, Eq, scale::Encode, scale::Decode,
+    )]
     #[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
     pub enum RegistrationState {
         RegistrationOpen,
+        #[default]
         RegistrationClosed,
     }
-    impl Default for RegistrationState {
-        fn default() -> Self {
-            RegistrationState::RegistrationClosed
-        }
-    }
-    #[derive(SpreadLayout, PackedLayout, Debug, PartialEq, Eq, scale::Encode, scale::Decode)]
+    #[derive(
+        SpreadLayout, PackedLayout, Debug, Default, PartialEq, Eq, scale::Encode, scale::Decode,
+    )]
     #[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
     pub enum ElectionState {
         ElectionOpen,
+        #[default]
         ElectionClosed,
     }
-    impl Default for ElectionState {
-        fn default() -> Self {
-            ElectionState::ElectionClosed
-        }
+    /// Lifecycle phase of an election, as returned by `get_phase`.
+    #[derive(Debug, PartialEq, Eq, scale::Encode, scale::Decode)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+    pub enum Phase {
+        Created,
+        RegistrationOpen,
+        Voting,
+        Closed,
+        Finalized,
+    }
+    /// Outcome recorded when an election is finalized.
+    #[derive(SpreadLayout, PackedLayout, Debug, PartialEq, Eq, scale::Encode, scale::Decode)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+    pub enum ElectionOutcome {
+        Passed,
+        FailedQuorum,
+        Tied,
+        NoVotes,
+    }
+    /// How `get_winner` picks between proposals tied for first place.
+    #[derive(
+        SpreadLayout,
+        PackedLayout,
+        Debug,
+        Default,
+        Clone,
+        PartialEq,
+        Eq,
+        scale::Encode,
+        scale::Decode,
+    )]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+    pub enum TieBreak {
+        /// The proposal listed first wins.
+        #[default]
+        FirstListed,
+        /// The proposal that received its first vote earliest wins.
+        EarliestFirstVote,
+        /// A tied proposal is picked using the chain's randomness, seeded with
+        /// the election id. The pick is made once at finalization and stored;
+        /// before that `get_winner` reports a provisional pick that can change
+        /// between blocks. It is not cryptographically secure: block authors
+        /// can influence it.
+        Random,
+    }
+    /// Optional settings for an election, fixed at creation.
+    #[derive(
+        SpreadLayout,
+        PackedLayout,
+        Debug,
+        Default,
+        Clone,
+        PartialEq,
+        Eq,
+        scale::Encode,
+        scale::Decode,
+    )]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+    pub struct ElectionConfig {
+        /// Merkle root of the accounts allowed to register. When set, voters
+        /// can only register through `register_with_proof`.
+        pub allowlist_root: Option<[u8; 32]>,
+        /// Maximum total votes per proposal, in the same order as the
+        /// proposals. Empty means no caps; a cap of 0 leaves that proposal
+        /// uncapped.
+        pub caps: Vec<u128>,
+        /// Lets voters add a new proposal by voting for it while the
+        /// election is open, even though the ballot is otherwise locked.
+        pub allow_writeins: bool,
+        /// Trims leading and trailing spaces and lowercases the name before
+        /// the uniqueness check and storage. Only ASCII is handled; other
+        /// bytes are kept as they are.
+        pub normalize_names: bool,
+        /// One person, one vote: `vote` always spends the voter's full weight.
+        /// The weight argument must be 0 or exactly that full weight, and
+        /// `vote_split` is rejected.
+        pub single_vote: bool,
+        /// Minimum total votes cast for the election to pass. 0 means no
+        /// quorum.
+        pub quorum: u128,
+        /// Maximum number of registered voters. 0 means no limit.
+        pub max_voters: u32,
+        /// Total token supply that results are measured against in
+        /// `get_results_vs_supply`. 0 means results are measured against the
+        /// votes cast.
+        pub total_supply: u128,
+        /// Length of the voting window in milliseconds, starting when the
+        /// election is opened. Reported by `get_voting_window` and used by
+        /// `decay`.
+        pub voting_period: u64,
+        /// Makes votes count less the later they are cast: weight decays
+        /// linearly from full at the start of the voting window to nothing at
+        /// its end. The voter still spends the full weight, and revoking a
+        /// vote gives the full weight back.
+        pub decay: bool,
+        /// Milliseconds after `open_election` before votes are accepted.
+        pub voting_delay: u64,
+        /// Extra weight, in basis points, given to votes cast within
+        /// `early_window` milliseconds of the election opening. The voter
+        /// still spends only the weight they passed.
+        pub early_bonus_bps: u32,
+        /// Length of the early voting window in milliseconds.
+        pub early_window: u64,
+        /// Never registers voters implicitly: even without required
+        /// registration, only explicitly registered voters can vote.
+        pub strict: bool,
+        /// How `get_winner` resolves a tie for first place.
+        pub tie_break: TieBreak,
+        /// Hides tallies from everyone but the owner while the election is
+        /// open: every getter that reveals a tally returns `ResultsHidden`
+        /// until it closes. Vote events still name the voter but carry an
+        /// empty `proposal` and a `weight` and `new_total` of 0.
+        pub hide_results: bool,
+        /// Timestamp after which registration is refused even if it is
+        /// open. 0 means no deadline.
+        pub registration_deadline: Timestamp,
+        /// Emits `AnonymousVoted` with a salted hash of the voter instead of
+        /// `Voted` with their account. Only events are affected; the salt is
+        /// stored on-chain, so this hides voters from casual event readers,
+        /// not from someone who reads storage.
+        pub anonymize: bool,
+        /// Salt mixed into the voter hash when `anonymize` is set.
+        pub salt: [u8; 32],
+        /// Minimum number of distinct voters for the election to pass,
+        /// checked alongside `quorum`. 0 means no headcount requirement.
+        pub min_voters: u32,
     }
     pub type Result<T> = core::result::Result<T, Error>;
+    /// (proposal, votes) for each proposal of an election or round.
+    pub type ProposalResults = Vec<(Vec<u8>, u128)>;
+    /// (name, owner, require_registration, results) as returned by `export_election`.
+    pub type ElectionExport = (Vec<u8>, AccountId, bool, Vec<(Vec<u8>, u128)>);
+    /// (proposal_id, counted_weight, spent_weight) for each proposal a voter
+    /// backed. The counted weight includes decay and bonuses; only the spent
+    /// weight is refunded when the vote is revoked.
+    pub type VoterChoices = Vec<(u32, u128, u128)>;
+
+    /// Upper bound on the number of entries returned by paged queries.
+    const MAX_PAGE_SIZE: u32 = 25;
+    /// Upper bound on the number of proposals in a single election.
+    const MAX_PROPOSALS: usize = 100;
+    /// Bumped whenever the storage layout or message set changes.
+    const CONTRACT_VERSION: u32 = 1;
+    /// Storage layout version the code expects. A contract whose stored
+    /// version differs is mid-migration and rejects state changes.
+    const STORAGE_VERSION: u16 = 1;
+    const MOTION_FOR: &[u8] = b"For";
+    const MOTION_AGAINST: &[u8] = b"Against";
+
+    fn normalize_name(name: &[u8]) -> Vec<u8> {
+        let start = name.iter().position(|b| *b != b' ').unwrap_or(name.len());
+        let end = name
+            .iter()
+            .rposition(|b| *b != b' ')
+            .map_or(start, |i| i + 1);
+        name[start..end].to_ascii_lowercase()
+    }
 
     impl InkVotingDapp {
         #[ink(constructor)]
         pub fn new() -> Self {
-            ink_lang::utils::initialize_contract(|contract| Self::new_init(contract))
+            ink_lang::utils::initialize_contract(Self::new_init)
         }
 
         fn new_init(&mut self) {
             self.election_nonce = 1;
             self.election_count = 0;
+            self.admin = Self::env().caller();
+            self.storage_version = STORAGE_VERSION;
         }
 
         #[ink(message)]
@@ -140,8 +464,35 @@ mod ink_voting_dapp {
             required_registration: bool,
             proposals: Vec<Vec<u8>>,
         ) -> Result<()> {
+            self.create_election_with_config(
+                name,
+                required_registration,
+                proposals,
+                ElectionConfig::default(),
+            )
+        }
+
+        #[ink(message)]
+        pub fn create_election_with_config(
+            &mut self,
+            name: Vec<u8>,
+            required_registration: bool,
+            proposals: Vec<Vec<u8>>,
+            config: ElectionConfig,
+        ) -> Result<()> {
+            self.check_not_paused()?;
+            self.ensure_version()?;
+            self.check_creation_limit(&Self::env().caller())?;
+            let name = if config.normalize_names {
+                normalize_name(&name)
+            } else {
+                name
+            };
             self.check_double_election(&name)?;
             self.check_sufficient_proposals(&proposals)?;
+            if !config.caps.is_empty() && config.caps.len() != proposals.len() {
+                return Err(Error::InvalidCaps);
+            }
             let election_id = self.election_nonce;
             self.insert_election(
                 &name,
@@ -150,51 +501,394 @@ mod ink_voting_dapp {
                 required_registration,
                 &proposals,
             );
+            for (i, cap) in config.caps.iter().enumerate() {
+                self.proposal_caps.insert((election_id, i as u32 + 1), cap);
+            }
+            self.election_configs.insert(
+                election_id,
+                &ElectionConfig {
+                    caps: Vec::new(),
+                    ..config
+                },
+            );
             Self::env().emit_event(ElectionCreated {
-                name: name,
+                name,
                 id: election_id,
                 owner: Self::env().caller(),
                 require_registration: required_registration,
+                proposal_count: proposals.len() as u32,
             });
             self.election_nonce += 1;
             self.election_count += 1;
             Ok(())
         }
         #[ink(message)]
+        pub fn save_template(&mut self, name: Vec<u8>, proposals: Vec<Vec<u8>>) -> Result<()> {
+            self.ensure_version()?;
+            self.check_sufficient_proposals(&proposals)?;
+            self.templates
+                .insert((Self::env().caller(), name), &proposals);
+            Ok(())
+        }
+
+        #[ink(message)]
+        pub fn create_from_template(
+            &mut self,
+            election_name: Vec<u8>,
+            template_name: Vec<u8>,
+            required_registration: bool,
+        ) -> Result<()> {
+            let proposals = self
+                .templates
+                .get((Self::env().caller(), template_name.clone()))
+                .or_else(|| self.public_templates.get(template_name))
+                .ok_or(Error::TemplateNotFound)?;
+            self.create_election(election_name, required_registration, proposals)
+        }
+
+        /// Publishes the election's proposal set as a template any account
+        /// can pass to `create_from_template`. Public names are global.
+        #[ink(message)]
+        pub fn publish_as_template(
+            &mut self,
+            election_id: u32,
+            template_name: Vec<u8>,
+        ) -> Result<()> {
+            self.ensure_version()?;
+            self.check_id_existence(&election_id)?;
+            let caller = Self::env().caller();
+            self.only_owner(&election_id, &caller)?;
+            if self.public_templates.contains(&template_name) {
+                return Err(Error::TemplateExists);
+            }
+            let proposals = self.proposals_list.get(election_id).unwrap_or_default();
+            self.public_templates.insert(&template_name, &proposals);
+            Self::env().emit_event(PublishedTemplate {
+                election_id,
+                name: template_name,
+                publisher: caller,
+            });
+            Ok(())
+        }
+
+        #[ink(message)]
+        pub fn get_public_template(&self, name: Vec<u8>) -> Vec<Vec<u8>> {
+            self.public_templates.get(name).unwrap_or_default()
+        }
+
+        #[ink(message)]
+        pub fn get_template(&self, owner: AccountId, name: Vec<u8>) -> Vec<Vec<u8>> {
+            self.templates.get((owner, name)).unwrap_or_default()
+        }
+
+        #[ink(message)]
+        pub fn create_motion(&mut self, name: Vec<u8>, required_registration: bool) -> Result<()> {
+            let election_id = self.election_nonce;
+            self.create_election(
+                name,
+                required_registration,
+                vec![MOTION_FOR.to_vec(), MOTION_AGAINST.to_vec()],
+            )?;
+            self.motions.insert(election_id, &true);
+            Ok(())
+        }
+        #[ink(message)]
         pub fn vote(&mut self, election_id: u32, proposal: Vec<u8>, weight: u128) -> Result<()> {
+            self.cast_vote(election_id, proposal, weight, Self::env().caller())
+        }
+
+        /// Lets a relayer submit a vote signed by `voter`. The signature is an
+        /// ECDSA signature over the Blake2x256 hash of the SCALE encoded
+        /// (contract, election_id, proposal, weight, nonce), where contract is
+        /// this contract's account id and nonce is the voter's current
+        /// `get_authorization_nonce`.
+        #[ink(message)]
+        pub fn vote_with_authorization(
+            &mut self,
+            election_id: u32,
+            proposal: Vec<u8>,
+            weight: u128,
+            voter: AccountId,
+            signature: [u8; 65],
+        ) -> Result<()> {
+            let nonce = self.get_authorization_nonce(voter);
+            let message = scale::Encode::encode(&(
+                Self::env().account_id(),
+                election_id,
+                &proposal,
+                weight,
+                nonce,
+            ));
+            let message_hash = Self::env().hash_bytes::<ink_env::hash::Blake2x256>(&message);
+            let public_key = Self::env()
+                .ecdsa_recover(&signature, &message_hash)
+                .map_err(|_| Error::SignatureInvalid)?;
+            let signer = Self::env().hash_bytes::<ink_env::hash::Blake2x256>(&public_key);
+            if AccountId::from(signer) != voter {
+                return Err(Error::SignatureInvalid);
+            }
+            self.cast_vote(election_id, proposal, weight, voter)?;
+            self.authorization_nonces.insert(voter, &(nonce + 1));
+            Ok(())
+        }
+
+        #[ink(message)]
+        pub fn get_authorization_nonce(&self, voter: AccountId) -> u64 {
+            self.authorization_nonces.get(voter).unwrap_or_default()
+        }
+
+        /// Runs the checks `vote` would run for `voter` without changing any
+        /// state, returning the error they would hit. An unregistered voter in
+        /// an election without registration is checked as if auto-registered.
+        #[ink(message)]
+        pub fn can_vote(&self, election_id: u32, voter: AccountId, weight: u128) -> Result<()> {
+            self.check_can_cast(&election_id, &voter, weight)?;
+            Ok(())
+        }
+
+        fn cast_vote(
+            &mut self,
+            election_id: u32,
+            proposal: Vec<u8>,
+            weight: u128,
+            voter_address: AccountId,
+        ) -> Result<()> {
+            let weight = self.check_can_cast(&election_id, &voter_address, weight)?;
+            self.check_if_registration_needed(&election_id, &voter_address)?;
+            if self.allows_writeins(&election_id) && !proposal.is_empty() {
+                if self.check_proposal_valid(&election_id, &proposal).is_err() {
+                    self.add_proposal_to_list(&election_id, &proposal)?;
+                    Self::env().emit_event(WriteInAdded {
+                        election_id,
+                        proposal: proposal.clone(),
+                    });
+                }
+            } else {
+                self.check_proposal_valid(&election_id, &proposal)?;
+            }
+            self.check_proposal_cap(&election_id, &proposal, &weight)?;
+            let new_total = self._vote(&election_id, &proposal, &voter_address, &weight)?;
+            self.mark_voting_started(&election_id);
+            self.emit_voted(election_id, voter_address, proposal, weight, new_total);
+            Ok(())
+        }
+
+        /// Spreads the caller's weight over several proposals in one call. The
+        /// voter is marked as having voted even if part of their weight is left.
+        /// Single-vote elections reject it with `SplitNotAllowed`.
+        #[ink(message)]
+        pub fn vote_split(
+            &mut self,
+            election_id: u32,
+            allocations: Vec<(Vec<u8>, u128)>,
+        ) -> Result<()> {
+            self.ensure_version()?;
             self.check_id_existence(&election_id)?;
+            if self.is_single_vote(&election_id) {
+                return Err(Error::SplitNotAllowed);
+            }
+            self.check_not_finalized(&election_id)?;
             self.check_election_open(&election_id)?;
+            self.check_voting_started(&election_id)?;
+            if allocations.is_empty() {
+                return Err(Error::InvalidProposal);
+            }
+            let mut total_weight: u128 = 0;
+            for (i, (proposal, weight)) in allocations.iter().enumerate() {
+                self.check_proposal_valid(&election_id, proposal)?;
+                if allocations[..i].iter().any(|(other, _)| other == proposal) {
+                    return Err(Error::InvalidProposal);
+                }
+                self.check_proposal_cap(&election_id, proposal, weight)?;
+                // `_vote` writes as it goes, so every tally is checked first.
+                let proposal_id = self.proposals_ids.get((election_id, proposal)).unwrap();
+                self.vote_proposals
+                    .get((election_id, proposal_id))
+                    .unwrap_or_default()
+                    .checked_add(self._effective_weight(&election_id, weight))
+                    .ok_or(Error::TallyOverflow)?;
+                total_weight = total_weight
+                    .checked_add(*weight)
+                    .ok_or(Error::WeightExceeded)?;
+            }
+            self.check_max_vote_weight(&total_weight)?;
             let voter_address = Self::env().caller();
             self.check_if_registration_needed(&election_id, &voter_address)?;
-            self.check_voter_can_vote(&election_id, &voter_address, &weight)?;
-            self.check_proposal_valid(&election_id, &proposal)?;
-            self._vote(&election_id, &proposal, &voter_address, &weight);
-            Self::env().emit_event(Voted {
-                voter: voter_address,
-                proposal: proposal,
-                weight: weight,
-            });
+            let (voter_weight, voter_has_voted) =
+                self.voters.get((election_id, voter_address)).unwrap();
+            if voter_has_voted {
+                return Err(Error::VoterHasAlreadyVoted);
+            }
+            if total_weight > voter_weight {
+                return Err(Error::WeightExceeded);
+            }
+            for (proposal, weight) in allocations {
+                let new_total = self._vote(&election_id, &proposal, &voter_address, &weight)?;
+                self.emit_voted(election_id, voter_address, proposal, weight, new_total);
+            }
+            self.voters.insert(
+                (election_id, voter_address),
+                &(voter_weight - total_weight, true),
+            );
+            self.mark_voting_started(&election_id);
+            Ok(())
+        }
+
+        #[ink(message)]
+        pub fn revoke_vote(&mut self, election_id: u32) -> Result<()> {
+            self.ensure_version()?;
+            self.check_id_existence(&election_id)?;
+            self.check_election_open(&election_id)?;
+            let voter_address = Self::env().caller();
+            let choices = self
+                .voter_choices
+                .get((election_id, voter_address))
+                .unwrap_or_default();
+            if choices.is_empty() {
+                return Err(Error::HasNotVoted);
+            }
+            let weight = self._revoke_vote(&election_id, &voter_address, &choices);
+            self.emit_vote_revoked(election_id, voter_address, weight);
+            Ok(())
+        }
+
+        /// Stores the results of the current round and starts the next one.
+        /// Tallies are reset and every vote is returned to its voter, so each
+        /// round is voted on from scratch.
+        #[ink(message)]
+        pub fn close_round(&mut self, election_id: u32) -> Result<()> {
+            self.ensure_version()?;
+            self.check_id_existence(&election_id)?;
+            self.only_owner(&election_id, &Self::env().caller())?;
+            self.check_not_finalized(&election_id)?;
+            let round = self.get_current_round(election_id);
+            self.round_results
+                .insert((election_id, round), &self._result_election(election_id));
+            for voter in self.voter_list.get(election_id).unwrap_or_default() {
+                let choices = self
+                    .voter_choices
+                    .get((election_id, voter))
+                    .unwrap_or_default();
+                if !choices.is_empty() {
+                    self._revoke_vote(&election_id, &voter, &choices);
+                }
+            }
+            for (proposal_id, _) in self.get_proposals_with_ids(election_id) {
+                self.vote_proposals.insert((election_id, proposal_id), &0);
+                self.proposal_first_vote_ts
+                    .remove((election_id, proposal_id));
+            }
+            self.current_round.insert(election_id, &(round + 1));
+            Self::env().emit_event(RoundClosed { election_id, round });
             Ok(())
         }
 
+        /// Returns the 0-based index of the round currently being voted on.
+        #[ink(message)]
+        pub fn get_current_round(&self, election_id: u32) -> u32 {
+            self.current_round.get(election_id).unwrap_or_default()
+        }
+
+        /// Returns the results stored when `round` was closed, or an empty list
+        /// if it has not been closed yet.
+        #[ink(message)]
+        pub fn get_round_result(&self, election_id: u32, round: u32) -> ProposalResults {
+            self.round_results
+                .get((election_id, round))
+                .unwrap_or_default()
+        }
+
+        /// Registers the caller. On allowlisted elections this returns
+        /// `ProofRequired` for every caller, listed or not, since the contract
+        /// cannot tell them apart without a proof.
         #[ink(message)]
         pub fn register_me(&mut self, election_id: u32) -> Result<()> {
             self.register(election_id, Self::env().caller())?;
             Ok(())
         }
 
+        /// Registers `voter`. Allowlisted elections only know the tree root, so
+        /// they return `ProofRequired`: voters there must prove membership with
+        /// `register_with_proof`.
         #[ink(message)]
         pub fn register(&mut self, election_id: u32, voter: AccountId) -> Result<()> {
+            self.ensure_version()?;
+            if self.get_allowlist_root(election_id).is_some() {
+                return Err(Error::ProofRequired);
+            }
+            self._register(election_id, voter)
+        }
+
+        /// Registers several voters at once, skipping those already registered.
+        /// A single `BatchRegistered` event reports how many were added instead
+        /// of one `Registered` event per voter.
+        #[ink(message)]
+        pub fn register_batch(&mut self, election_id: u32, voters: Vec<AccountId>) -> Result<()> {
+            self.ensure_version()?;
+            self.check_id_existence(&election_id)?;
+            if self.get_allowlist_root(election_id).is_some() {
+                return Err(Error::ProofRequired);
+            }
+            self.check_registration_open(&election_id)?;
+            let mut new_voters = Vec::new();
+            for voter in voters {
+                if !self.is_voter_registered(&election_id, &voter) && !new_voters.contains(&voter) {
+                    new_voters.push(voter);
+                }
+            }
+            if new_voters.len() as u32 > self.get_remaining_slots(election_id) {
+                return Err(Error::VoterLimitReached);
+            }
+            for voter in &new_voters {
+                self.register_voter(voter, &election_id)?;
+            }
+            Self::env().emit_event(BatchRegistered {
+                election_id,
+                count: new_voters.len() as u32,
+            });
+            Ok(())
+        }
+
+        /// Registers the caller in an allowlisted election. `proof` holds the
+        /// sibling hashes from the caller's leaf, `blake2x256(account)`, up to the
+        /// root; each pair is hashed in ascending byte order.
+        #[ink(message)]
+        pub fn register_with_proof(
+            &mut self,
+            election_id: u32,
+            proof: Vec<[u8; 32]>,
+        ) -> Result<()> {
+            self.ensure_version()?;
+            self.check_id_existence(&election_id)?;
+            let voter = Self::env().caller();
+            match self.get_allowlist_root(election_id) {
+                Some(root) if self.verify_proof(&root, &voter, &proof) => {
+                    self._register(election_id, voter)
+                }
+                _ => Err(Error::InvalidProof),
+            }
+        }
+
+        /// Returns whether `proof` puts the caller on the election's allowlist.
+        /// Only the Merkle root is stored, so membership cannot be checked
+        /// without the caller's proof. Elections without an allowlist accept
+        /// everyone.
+        #[ink(message)]
+        pub fn am_i_allowlisted(&self, election_id: u32, proof: Vec<[u8; 32]>) -> bool {
+            match self.get_allowlist_root(election_id) {
+                Some(root) => self.verify_proof(&root, &Self::env().caller(), &proof),
+                None => true,
+            }
+        }
+
+        fn _register(&mut self, election_id: u32, voter: AccountId) -> Result<()> {
             self.check_id_existence(&election_id)?;
             self.check_registration_open(&election_id)?;
             if self.is_voter_registered(&election_id, &voter) {
                 return Err(Error::VoterAlreadyRegistered);
             };
-            self.register_voter(&voter, &election_id);
-            Self::env().emit_event(Registered {
-                voter: voter,
-                election_id: election_id,
-            });
+            self.register_voter(&voter, &election_id)?;
+            Self::env().emit_event(Registered { voter, election_id });
             Ok(())
         }
 
@@ -205,29 +899,97 @@ mod ink_voting_dapp {
             delegate: AccountId,
             weight: u128,
         ) -> Result<()> {
+            self.ensure_version()?;
             self.check_id_existence(&election_id)?;
+            self.check_not_finalized(&election_id)?;
+            self.check_election_open(&election_id)?;
             let delegator = Self::env().caller();
-            self.check_if_registration_needed(&election_id, &delegator)?;
-            self.check_if_registration_needed(&election_id, &delegate)?;
+            if delegate == delegator {
+                return Err(Error::SelfDelegation);
+            }
+            self.check_if_registration_needed(&election_id, &delegator)
+                .map_err(|error| match error {
+                    Error::VoterNotRegistred => Error::DelegatorNotRegistered,
+                    error => error,
+                })?;
+            self.check_if_registration_needed(&election_id, &delegate)
+                .map_err(|error| match error {
+                    Error::VoterNotRegistred => Error::DelegateNotRegistered,
+                    error => error,
+                })?;
             self.check_voter_can_vote(&election_id, &delegator, &weight)?;
-            self.delegate(&election_id, &delegate, &delegator, &weight);
+            self.delegate(&election_id, &delegate, &delegator, &weight)?;
             Self::env().emit_event(Delegate {
-                election_id: election_id,
-                delegate: delegate,
-                delegator: delegator,
+                election_id,
+                delegate,
+                delegator,
             });
             Ok(())
         }
 
+        /// Sets the weight of each listed account before the election first
+        /// opens, registering new accounts and overwriting the weight of
+        /// registered voters who have not voted yet. Once the election has been
+        /// opened, even if closed again, it returns `ElectionAlreadyOpened`.
+        /// A voter listed twice keeps the last weight.
+        #[ink(message)]
+        pub fn seed_weights(
+            &mut self,
+            election_id: u32,
+            entries: Vec<(AccountId, u128)>,
+        ) -> Result<()> {
+            self.ensure_version()?;
+            self.check_id_existence(&election_id)?;
+            self.only_owner(&election_id, &Self::env().caller())?;
+            self.check_not_finalized(&election_id)?;
+            if self._is_election_open(&election_id) {
+                return Err(Error::ElectionStillOpen);
+            }
+            if self.opened_at.contains(election_id) {
+                return Err(Error::ElectionAlreadyOpened);
+            }
+            let mut new_voters = Vec::new();
+            for (voter, _) in &entries {
+                let has_choices = !self
+                    .voter_choices
+                    .get((election_id, voter))
+                    .unwrap_or_default()
+                    .is_empty();
+                if self.has_voter_voted(election_id, *voter) || has_choices {
+                    return Err(Error::VoterHasAlreadyVoted);
+                }
+                if !self.is_voter_registered(&election_id, voter) && !new_voters.contains(voter) {
+                    new_voters.push(*voter);
+                }
+            }
+            if new_voters.len() as u32 > self.get_remaining_slots(election_id) {
+                return Err(Error::VoterLimitReached);
+            }
+            for (voter, weight) in entries {
+                if !self.is_voter_registered(&election_id, &voter) {
+                    self.register_voter(&voter, &election_id)?;
+                }
+                self.voters.insert((election_id, voter), &(weight, false));
+                Self::env().emit_event(WeightAdjusted {
+                    election_id,
+                    voter,
+                    weight,
+                });
+            }
+            Ok(())
+        }
+
         #[ink(message)]
         pub fn open_registration(&mut self, election_id: u32) -> Result<()> {
+            self.ensure_version()?;
             self.check_id_existence(&election_id)?;
             self.only_owner(&election_id, &Self::env().caller())?;
+            self.check_not_finalized(&election_id)?;
             let mut election = self.elections.get(election_id).unwrap();
             election.2 = RegistrationState::RegistrationOpen;
             self.elections.insert(election_id, &election);
             Self::env().emit_event(OpenRegistration {
-                election_id: election_id,
+                election_id,
                 date: Self::env().block_timestamp(),
             });
             Ok(())
@@ -235,27 +997,46 @@ mod ink_voting_dapp {
 
         #[ink(message)]
         pub fn close_registration(&mut self, election_id: u32) -> Result<()> {
+            self.ensure_version()?;
             self.check_id_existence(&election_id)?;
             self.only_owner(&election_id, &Self::env().caller())?;
             let mut election = self.elections.get(election_id).unwrap();
             election.2 = RegistrationState::RegistrationClosed;
             self.elections.insert(election_id, &election);
             Self::env().emit_event(CloseRegistration {
-                election_id: election_id,
+                election_id,
                 date: Self::env().block_timestamp(),
             });
             Ok(())
         }
 
+        /// Opens the election for voting. The first opening time is kept when
+        /// a closed election is reopened, so `voting_delay`, `decay` and the
+        /// early bonus window are not restarted.
         #[ink(message)]
         pub fn open_election(&mut self, election_id: u32) -> Result<()> {
+            self.ensure_version()?;
             self.check_id_existence(&election_id)?;
             self.only_owner(&election_id, &Self::env().caller())?;
+            self.check_not_finalized(&election_id)?;
+            if self.is_force_closed(election_id) {
+                return Err(Error::ElectionForceClosed);
+            }
+            if self._is_election_open(&election_id) {
+                return Err(Error::ElectionStillOpen);
+            }
+            self.proposals_locked.insert(election_id, &true);
+            self.eligible_weight
+                .insert(election_id, &self.total_voter_weight(&election_id));
             let mut election = self.elections.get(election_id).unwrap();
             election.3 = ElectionState::ElectionOpen;
             self.elections.insert(election_id, &election);
+            if !self.opened_at.contains(election_id) {
+                self.opened_at
+                    .insert(election_id, &Self::env().block_timestamp());
+            }
             Self::env().emit_event(OpenElection {
-                election_id: election_id,
+                election_id,
                 date: Self::env().block_timestamp(),
             });
             Ok(())
@@ -263,176 +1044,1225 @@ mod ink_voting_dapp {
 
         #[ink(message)]
         pub fn close_election(&mut self, election_id: u32) -> Result<()> {
+            self.ensure_version()?;
             self.check_id_existence(&election_id)?;
             self.only_owner(&election_id, &Self::env().caller())?;
-            let mut election = self.elections.get(election_id).unwrap();
-            election.3 = ElectionState::ElectionClosed;
-            self.elections.insert(election_id, &election);
-            Self::env().emit_event(CloseElection {
-                election_id: election_id,
-                date: Self::env().block_timestamp(),
+            self._close_election(&election_id);
+            Ok(())
+        }
+
+        /// Freezes the result of a closed election. A finalized election can
+        /// no longer be reopened, voted on or delegated in, and its ballot can
+        /// no longer be edited, even if it was never opened.
+        ///
+        /// All storage writes happen before `FinalizeElection` is emitted, so
+        /// an indexer reacting to the event always finds the stored result.
+        #[ink(message)]
+        pub fn finalize_election(&mut self, election_id: u32) -> Result<()> {
+            self.ensure_version()?;
+            self.check_id_existence(&election_id)?;
+            self.only_owner(&election_id, &Self::env().caller())?;
+            self.check_not_finalized(&election_id)?;
+            if self._is_election_open(&election_id) {
+                return Err(Error::ElectionStillOpen);
+            }
+            let (winner, votes) = self._winner(election_id);
+            self.finalized_results
+                .insert(election_id, &(winner.clone(), votes));
+            self.outcomes
+                .insert(election_id, &self.compute_outcome(&election_id));
+            Self::env().emit_event(FinalizeElection {
+                election_id,
+                winner,
+                votes,
+            });
+            Ok(())
+        }
+
+        /// Closes the election and finalizes its result in a single call.
+        #[ink(message)]
+        pub fn close_and_finalize(&mut self, election_id: u32) -> Result<()> {
+            self.ensure_version()?;
+            self.check_id_existence(&election_id)?;
+            self.only_owner(&election_id, &Self::env().caller())?;
+            self.check_not_finalized(&election_id)?;
+            self._close_election(&election_id);
+            self.finalize_election(election_id)
+        }
+
+        /// Closes any election in an emergency. The owner cannot reopen a
+        /// force-closed election; it can only be finalized.
+        #[ink(message)]
+        pub fn admin_force_close_election(&mut self, election_id: u32) -> Result<()> {
+            self.ensure_version()?;
+            self.check_id_existence(&election_id)?;
+            let admin = Self::env().caller();
+            self.only_admin(&admin)?;
+            self._close_election(&election_id);
+            self.force_closed.insert(election_id, &true);
+            Self::env().emit_event(AdminForceClosed { election_id, admin });
+            Ok(())
+        }
+
+        /// Frees the per-voter storage of a finalized election. The finalized
+        /// result is kept, but voter weights and choices read as defaults
+        /// afterwards.
+        #[ink(message)]
+        pub fn admin_archive_election(&mut self, election_id: u32) -> Result<()> {
+            self.ensure_version()?;
+            self.check_id_existence(&election_id)?;
+            self.only_admin(&Self::env().caller())?;
+            if !self.is_finalized(election_id) {
+                return Err(Error::ElectionNotFinalized);
+            }
+            for voter in self.voter_list.get(election_id).unwrap_or_default() {
+                self.voters.remove((election_id, voter));
+                self.voter_choices.remove((election_id, voter));
+                self.delegated_in.remove((election_id, voter));
+                let mut voter_elections = self.voter_elections.get(voter).unwrap_or_default();
+                voter_elections.retain(|id| *id != election_id);
+                self.voter_elections.insert(voter, &voter_elections);
+            }
+            self.voter_list.remove(election_id);
+            Self::env().emit_event(Archived { election_id });
+            Ok(())
+        }
+
+        #[ink(message)]
+        pub fn lock_proposals(&mut self, election_id: u32) -> Result<()> {
+            self.ensure_version()?;
+            self.check_id_existence(&election_id)?;
+            self.only_owner(&election_id, &Self::env().caller())?;
+            self.check_not_finalized(&election_id)?;
+            self.check_proposals_unlocked(&election_id)?;
+            self.proposals_locked.insert(election_id, &true);
+            Ok(())
+        }
+
+        /// Removes a proposal before the ballot is locked. The remaining
+        /// proposals keep their order and are renumbered. The last proposal
+        /// cannot be removed.
+        #[ink(message)]
+        pub fn remove_proposal(&mut self, election_id: u32, proposal: Vec<u8>) -> Result<()> {
+            self.ensure_version()?;
+            self.check_id_existence(&election_id)?;
+            self.only_owner(&election_id, &Self::env().caller())?;
+            self.check_not_finalized(&election_id)?;
+            self.check_proposals_unlocked(&election_id)?;
+            self.check_proposal_valid(&election_id, &proposal)?;
+            let mut proposals = self.proposals_list.get(election_id).unwrap_or_default();
+            if proposals.len() <= 1 {
+                return Err(Error::InsufficientProposals);
+            }
+            let mut caps = Vec::new();
+            for (i, name) in proposals.iter().enumerate() {
+                let proposal_id = i as u32 + 1;
+                caps.push(
+                    self.proposal_caps
+                        .get((election_id, proposal_id))
+                        .unwrap_or_default(),
+                );
+                self.proposals_ids.remove((election_id, name));
+                self.vote_proposals.remove((election_id, proposal_id));
+                self.proposal_caps.remove((election_id, proposal_id));
+            }
+            let index = proposals.iter().position(|name| *name == proposal).unwrap();
+            proposals.remove(index);
+            caps.remove(index);
+            if let Some(order) = self.proposal_order.get(election_id) {
+                let removed_id = index as u32 + 1;
+                let order: Vec<u32> = order
+                    .into_iter()
+                    .filter(|proposal_id| *proposal_id != removed_id)
+                    .map(|proposal_id| {
+                        if proposal_id > removed_id {
+                            proposal_id - 1
+                        } else {
+                            proposal_id
+                        }
+                    })
+                    .collect();
+                self.proposal_order.insert(election_id, &order);
+            }
+            for (i, name) in proposals.iter().enumerate() {
+                self.insert_proposal(&election_id, name, &(i as u32));
+                if caps[i] != 0 {
+                    self.proposal_caps
+                        .insert((election_id, i as u32 + 1), &caps[i]);
+                }
+            }
+            self.proposals_list.insert(election_id, &proposals);
+            Ok(())
+        }
+
+        /// Overwrites the election's free-form metadata. Metadata does not
+        /// affect voting, so it can be changed at any stage.
+        #[ink(message)]
+        pub fn update_metadata(&mut self, election_id: u32, metadata: Vec<u8>) -> Result<()> {
+            self.ensure_version()?;
+            self.check_id_existence(&election_id)?;
+            self.only_owner(&election_id, &Self::env().caller())?;
+            self.elections_metadata.insert(election_id, &metadata);
+            Self::env().emit_event(MetadataUpdated {
+                election_id,
+                metadata,
             });
             Ok(())
         }
 
+        #[ink(message)]
+        pub fn get_metadata(&self, election_id: u32) -> Vec<u8> {
+            self.elections_metadata.get(election_id).unwrap_or_default()
+        }
+
+        /// Sets the display order of the ballot. `order` must list every
+        /// proposal id exactly once.
+        #[ink(message)]
+        pub fn reorder_proposals(&mut self, election_id: u32, order: Vec<u32>) -> Result<()> {
+            self.ensure_version()?;
+            self.check_id_existence(&election_id)?;
+            self.only_owner(&election_id, &Self::env().caller())?;
+            self.check_not_finalized(&election_id)?;
+            self.check_proposals_unlocked(&election_id)?;
+            let proposal_count = self
+                .proposals_list
+                .get(election_id)
+                .unwrap_or_default()
+                .len() as u32;
+            let mut sorted = order.clone();
+            sorted.sort_unstable();
+            if !sorted.into_iter().eq(1..=proposal_count) {
+                return Err(Error::InvalidOrder);
+            }
+            self.proposal_order.insert(election_id, &order);
+            Ok(())
+        }
+
+        /// Returns proposal ids in display order, which is creation order
+        /// unless the owner called `reorder_proposals`.
+        #[ink(message)]
+        pub fn get_proposal_order(&self, election_id: u32) -> Vec<u32> {
+            self.proposal_order.get(election_id).unwrap_or_else(|| {
+                let proposal_count = self
+                    .proposals_list
+                    .get(election_id)
+                    .unwrap_or_default()
+                    .len() as u32;
+                (1..=proposal_count).collect()
+            })
+        }
+
+        #[ink(message)]
+        pub fn has_votable_proposals(&self, election_id: u32) -> bool {
+            !self
+                .proposals_list
+                .get(election_id)
+                .unwrap_or_default()
+                .is_empty()
+        }
+
+        #[ink(message)]
+        pub fn close_all(&mut self, election_id: u32) -> Result<()> {
+            self.close_registration(election_id)?;
+            self.close_election(election_id)
+        }
+
         #[ink(message)]
         pub fn change_ownership(&mut self, election_id: u32, new_owner: AccountId) -> Result<()> {
+            self.ensure_version()?;
             self.check_id_existence(&election_id)?;
             self.only_owner(&election_id, &Self::env().caller())?;
-            let mut election = self.elections.get(election_id).unwrap();
-            election.0 = new_owner;
-            self.elections.insert(election_id, &election);
+            if self.is_owner(&new_owner, &election_id) {
+                return Err(Error::SameOwner);
+            }
+            self.set_owner(&election_id, &new_owner);
+            Self::env().emit_event(ChangeOwnership {
+                election_id,
+                new_owner,
+            });
+            Ok(())
+        }
+
+        /// First step of a two-step transfer: the candidate becomes owner only
+        /// once they call `accept_ownership`.
+        #[ink(message)]
+        pub fn propose_new_owner(&mut self, election_id: u32, candidate: AccountId) -> Result<()> {
+            self.ensure_version()?;
+            self.check_id_existence(&election_id)?;
+            self.only_owner(&election_id, &Self::env().caller())?;
+            self.pending_owners.insert(election_id, &candidate);
+            Self::env().emit_event(OwnershipProposed {
+                election_id,
+                candidate,
+            });
+            Ok(())
+        }
+
+        #[ink(message)]
+        pub fn accept_ownership(&mut self, election_id: u32) -> Result<()> {
+            self.ensure_version()?;
+            self.check_id_existence(&election_id)?;
+            let new_owner = Self::env().caller();
+            if self.pending_owners.get(election_id) != Some(new_owner) {
+                return Err(Error::NotPendingOwner);
+            }
+            self.set_owner(&election_id, &new_owner);
             Self::env().emit_event(ChangeOwnership {
-                election_id: election_id,
-                new_owner: new_owner,
+                election_id,
+                new_owner,
             });
             Ok(())
         }
 
+        #[ink(message)]
+        pub fn transfer_all_ownership(&mut self, new_owner: AccountId) -> Result<()> {
+            self.ensure_version()?;
+            let owner = Self::env().caller();
+            if new_owner == owner {
+                return Err(Error::SameOwner);
+            }
+            for election_id in self.owner_elections.get(owner).unwrap_or_default() {
+                self.set_owner(&election_id, &new_owner);
+                Self::env().emit_event(ChangeOwnership {
+                    election_id,
+                    new_owner,
+                });
+            }
+            Ok(())
+        }
+
+        #[ink(message)]
+        pub fn get_owner_election_count(&self, owner: AccountId) -> u32 {
+            self.owner_elections.get(owner).unwrap_or_default().len() as u32
+        }
+
+        /// Returns the current block timestamp so clients can use the chain's
+        /// clock rather than their own.
+        #[ink(message)]
+        pub fn now(&self) -> Timestamp {
+            Self::env().block_timestamp()
+        }
+
+        #[ink(message)]
+        pub fn get_version(&self) -> u32 {
+            CONTRACT_VERSION
+        }
+
+        #[ink(message)]
+        pub fn get_storage_version(&self) -> u16 {
+            self.storage_version
+        }
+
+        #[ink(message)]
+        pub fn get_admin(&self) -> AccountId {
+            self.admin
+        }
+
+        #[ink(message)]
+        pub fn set_paused(&mut self, paused: bool) -> Result<()> {
+            self.only_admin(&Self::env().caller())?;
+            self.paused = paused;
+            Ok(())
+        }
+
+        #[ink(message)]
+        pub fn is_paused(&self) -> bool {
+            self.paused
+        }
+
+        /// Limits how many elections one account can own. 0 means no limit.
+        #[ink(message)]
+        pub fn set_max_elections_per_account(&mut self, max_elections: u32) -> Result<()> {
+            self.only_admin(&Self::env().caller())?;
+            self.max_elections_per_account = max_elections;
+            Ok(())
+        }
+
+        #[ink(message)]
+        pub fn get_max_elections_per_account(&self) -> u32 {
+            self.max_elections_per_account
+        }
+
+        /// Caps the weight a single vote message can spend. 0 means no cap.
+        #[ink(message)]
+        pub fn set_max_vote_weight(&mut self, max_weight: u128) -> Result<()> {
+            self.only_admin(&Self::env().caller())?;
+            self.max_vote_weight = max_weight;
+            Ok(())
+        }
+
+        #[ink(message)]
+        pub fn get_max_vote_weight(&self) -> u128 {
+            self.max_vote_weight
+        }
+
         #[ink(message)]
         pub fn get_number_elections(&self) -> u32 {
             self.election_count
         }
 
+        /// Counts the elections currently open for voting. This reads every
+        /// election id, so its cost grows with the number of elections ever created.
+        #[ink(message)]
+        pub fn count_open_elections(&self) -> u32 {
+            (1..self.election_nonce)
+                .filter(|id| self._election_id_exists(id) && self._is_election_open(id))
+                .count() as u32
+        }
+
         #[ink(message)]
         pub fn get_election_id(&self, name: Vec<u8>) -> u32 {
             self.elections_ids.get(name).unwrap_or_default()
         }
 
+        #[ink(message)]
+        pub fn try_get_election_id(&self, name: Vec<u8>) -> Result<u32> {
+            self.elections_ids.get(name).ok_or(Error::ElectionNotValid)
+        }
+
         #[ink(message)]
         pub fn get_election_list(&self) -> Vec<Vec<u8>> {
             self.elections_list.clone()
         }
 
+        /// Returns (id, name, registration_open, election_open) for up to
+        /// `MAX_PAGE_SIZE` elections, starting at position `start` in creation order.
         #[ink(message)]
-        pub fn get_proposal_for_election(&self, name: Vec<u8>) -> Vec<Vec<u8>> {
-            let election_id = self.elections_ids.get(&name).unwrap_or_default();
-            self.proposals_list.get(election_id).unwrap_or_default()
+        pub fn get_all_elections_paged(
+            &self,
+            start: u32,
+            limit: u32,
+        ) -> Vec<(u32, Vec<u8>, bool, bool)> {
+            self.elections_list
+                .iter()
+                .skip(start as usize)
+                .take(limit.min(MAX_PAGE_SIZE) as usize)
+                .map(|name| {
+                    let election_id = self.elections_ids.get(name).unwrap_or_default();
+                    (
+                        election_id,
+                        name.clone(),
+                        self._is_registration_open(&election_id),
+                        self._is_election_open(&election_id),
+                    )
+                })
+                .collect()
         }
 
+        /// Returns everything needed to archive an election off-chain.
         #[ink(message)]
-        pub fn get_owner_of_election(&self, election_id: u32) -> AccountId {
-            self.elections.get(election_id).unwrap_or_default().0
+        pub fn export_election(&self, election_id: u32) -> Result<ElectionExport> {
+            self.check_id_existence(&election_id)?;
+            self.check_results_visible(&election_id)?;
+            let (owner, required_registration, _, _) = self.elections.get(election_id).unwrap();
+            Ok((
+                self.election_name(&election_id),
+                owner,
+                required_registration,
+                self._result_election(election_id),
+            ))
         }
 
+        /// Returns `(voter, remaining_weight, voted)` for up to
+        /// `MAX_PAGE_SIZE` voters, starting at position `start` in registration order.
         #[ink(message)]
-        pub fn election_exists(&self, name: Vec<u8>) -> bool {
-            self._election_name_exists(&name)
+        pub fn export_roll(
+            &self,
+            election_id: u32,
+            start: u32,
+            limit: u32,
+        ) -> Vec<(AccountId, u128, bool)> {
+            self.voter_list
+                .get(election_id)
+                .unwrap_or_default()
+                .into_iter()
+                .skip(start as usize)
+                .take(limit.min(MAX_PAGE_SIZE) as usize)
+                .map(|voter| {
+                    let (weight, voted) = self.voters.get((election_id, voter)).unwrap_or_default();
+                    (voter, weight, voted)
+                })
+                .collect()
         }
+
         #[ink(message)]
-        pub fn is_election_open(&self, election_id: u32) -> bool {
-            self._is_election_open(&election_id)
+        pub fn get_proposal_for_election(&self, name: Vec<u8>) -> Vec<Vec<u8>> {
+            let election_id = self.elections_ids.get(&name).unwrap_or_default();
+            self.proposals_list.get(election_id).unwrap_or_default()
         }
+
         #[ink(message)]
-        pub fn is_registration_open(&self, election_id: u32) -> bool {
-            self._is_registration_open(&election_id)
+        pub fn get_proposal_by_index(&self, election_id: u32, index: u32) -> Vec<u8> {
+            self.proposals_list
+                .get(election_id)
+                .unwrap_or_default()
+                .get(index as usize)
+                .cloned()
+                .unwrap_or_default()
         }
+
         #[ink(message)]
-        pub fn is_account_registered(&self, election_id: u32, account: AccountId) -> bool {
-            self.voters.get((election_id, account)) != None
+        pub fn get_proposals_sorted(&self, election_id: u32) -> Vec<Vec<u8>> {
+            let mut proposals = self.proposals_list.get(election_id).unwrap_or_default();
+            proposals.sort();
+            proposals
         }
+
         #[ink(message)]
-        pub fn get_result_election(&self, election_id: u32) -> Vec<(Vec<u8>, u128)> {
+        pub fn get_proposals_with_ids(&self, election_id: u32) -> Vec<(u32, Vec<u8>)> {
             let mut result = Vec::new();
             for proposal in self.proposals_list.get(election_id).unwrap_or_default() {
                 let proposal_id = self
                     .proposals_ids
                     .get((election_id, &proposal))
                     .unwrap_or_default();
-                result.push((
-                    proposal,
-                    self.vote_proposals
-                        .get((election_id, proposal_id))
-                        .unwrap_or_default(),
-                ));
+                result.push((proposal_id, proposal));
             }
             result
         }
+
         #[ink(message)]
-        pub fn get_votes_proposal(&self, election_id: u32, proposal: Vec<u8>) -> u128 {
+        pub fn get_proposal_cap(&self, election_id: u32, proposal: Vec<u8>) -> u128 {
             let proposal_id = self
                 .proposals_ids
                 .get((election_id, proposal))
                 .unwrap_or_default();
-            self.vote_proposals
+            self.proposal_caps
                 .get((election_id, proposal_id))
                 .unwrap_or_default()
         }
 
         #[ink(message)]
-        pub fn get_winner(&self, election_id: u32) -> (Vec<u8>, u128) {
-            let mut winner = Vec::new();
-            let mut max_votes = 0;
-            let mut proposal_id;
-            let mut vote_proposal;
-            for proposal in self.proposals_list.get(election_id).unwrap_or_default() {
-                proposal_id = self
-                    .proposals_ids
-                    .get((election_id, &proposal))
-                    .unwrap_or_default();
-                vote_proposal = self
-                    .vote_proposals
-                    .get((election_id, proposal_id))
-                    .unwrap_or_default();
-                if vote_proposal > max_votes {
-                    winner = proposal;
-                    max_votes = self
-                        .vote_proposals
-                        .get((election_id, proposal_id))
-                        .unwrap_or_default()
-                }
-            }
-            (winner, max_votes)
+        pub fn get_owner_of_election(&self, election_id: u32) -> AccountId {
+            self.elections.get(election_id).unwrap_or_default().0
         }
 
         #[ink(message)]
-        pub fn get_voter_weigth(&self, election_id: u32, voter: AccountId) -> u128 {
-            self.voters.get((election_id, voter)).unwrap_or_default().0
+        pub fn get_created_at(&self, election_id: u32) -> Timestamp {
+            self.created_at.get(election_id).unwrap_or_default()
         }
 
         #[ink(message)]
-        pub fn has_voter_voted(&self, election_id: u32, voter: AccountId) -> bool {
-            self.voters.get((election_id, voter)).unwrap_or_default().1
+        pub fn election_exists(&self, name: Vec<u8>) -> bool {
+            self._election_name_exists(&name)
         }
-
-        fn subtract_weight(&mut self, election_id: &u32, voter: &AccountId, weight: &u128) {
-            let voter_weight = self.voters.get((election_id, voter)).unwrap().0;
-            if voter_weight - weight == 0 {
-                self.voters.insert((&election_id, &voter), &(0, true));
-            } else {
-                self.voters
-                    .insert((&election_id, &voter), &(voter_weight - weight, false));
-            }
+        #[ink(message)]
+        pub fn election_id_exists(&self, election_id: u32) -> bool {
+            self._election_id_exists(&election_id)
         }
-        fn register_voter(&mut self, voter: &AccountId, election_id: &u32) {
-            self.voters.insert((election_id, voter), &(1, false));
+        #[ink(message)]
+        pub fn proposal_exists(&self, election_id: u32, proposal: Vec<u8>) -> bool {
+            self.proposals_ids.contains((election_id, proposal))
         }
-        fn _election_name_exists(&self, name: &Vec<u8>) -> bool {
-            self.elections_ids.get(name) != None
+        #[ink(message)]
+        pub fn is_name_available(&self, name: Vec<u8>) -> bool {
+            !self._election_name_exists(&name)
+        }
+        #[ink(message)]
+        pub fn is_election_open(&self, election_id: u32) -> bool {
+            self._is_election_open(&election_id)
+        }
+        #[ink(message)]
+        pub fn is_registration_open(&self, election_id: u32) -> bool {
+            self._is_registration_open(&election_id)
+        }
+        /// Returns the lifecycle phase of the election. An election that has
+        /// been opened and closed again is `Closed` even if registration is
+        /// reopened afterwards.
+        #[ink(message)]
+        pub fn get_phase(&self, election_id: u32) -> Phase {
+            if self.is_finalized(election_id) {
+                Phase::Finalized
+            } else if self._is_election_open(&election_id) {
+                Phase::Voting
+            } else if self.opened_at.contains(election_id) {
+                Phase::Closed
+            } else if self._is_registration_open(&election_id) {
+                Phase::RegistrationOpen
+            } else {
+                Phase::Created
+            }
         }
 
-        fn _is_election_open(&self, election_id: &u32) -> bool {
-            self.elections.get(election_id).unwrap_or_default().3 == ElectionState::ElectionOpen
+        /// Returns the registration deadline, or 0 if there is none.
+        #[ink(message)]
+        pub fn get_registration_deadline(&self, election_id: u32) -> Timestamp {
+            self.election_configs
+                .get(election_id)
+                .unwrap_or_default()
+                .registration_deadline
         }
 
-        fn _is_registration_open(&self, election_id: &u32) -> bool {
-            self.elections.get(election_id).unwrap_or_default().2
-                == RegistrationState::RegistrationOpen
+        /// Returns (start, end) of the voting window. The start is 0 until the
+        /// election is opened and the end is 0 when no voting period is set.
+        #[ink(message)]
+        pub fn get_voting_window(&self, election_id: u32) -> (Timestamp, Timestamp) {
+            let opened_at = match self.opened_at.get(election_id) {
+                Some(opened_at) => opened_at,
+                None => return (0, 0),
+            };
+            let voting_period = self
+                .election_configs
+                .get(election_id)
+                .unwrap_or_default()
+                .voting_period;
+            if voting_period == 0 {
+                (opened_at, 0)
+            } else {
+                (opened_at, opened_at.saturating_add(voting_period))
+            }
         }
 
-        fn delegate(
-            &mut self,
-            election_id: &u32,
-            delegate: &AccountId,
-            delegator_address: &AccountId,
-            weight: &u128,
-        ) {
-            let weight_delegate = &self.voters.get((election_id, delegate)).unwrap().0;
-            self.voters
-                .insert((election_id, delegate), &(weight_delegate + weight, false));
-            self.subtract_weight(election_id, delegator_address, weight);
+        /// Returns the ids of elections in `phase`, looking at up to
+        /// `MAX_PAGE_SIZE` elections starting at position `start` in creation
+        /// order. A page can therefore hold fewer than `limit` ids even when
+        /// later elections match.
+        #[ink(message)]
+        pub fn get_elections_in_phase(&self, phase: Phase, start: u32, limit: u32) -> Vec<u32> {
+            self.id_page(start, limit)
+                .filter(|election_id| self.get_phase(*election_id) == phase)
+                .collect()
         }
 
+        /// Returns the ids of elections created between `from` and `to`,
+        /// inclusive, paged like `get_elections_in_phase`.
+        #[ink(message)]
+        pub fn get_elections_created_between(
+            &self,
+            from: Timestamp,
+            to: Timestamp,
+            start: u32,
+            limit: u32,
+        ) -> Vec<u32> {
+            self.id_page(start, limit)
+                .filter(|election_id| {
+                    let created_at = self.get_created_at(*election_id);
+                    from <= created_at && created_at <= to
+                })
+                .collect()
+        }
+
+        #[ink(message)]
+        pub fn get_registration_state(&self, election_id: u32) -> RegistrationState {
+            self.elections.get(election_id).unwrap_or_default().2
+        }
+        #[ink(message)]
+        pub fn get_election_state(&self, election_id: u32) -> ElectionState {
+            self.elections.get(election_id).unwrap_or_default().3
+        }
+        #[ink(message)]
+        pub fn get_allowlist_root(&self, election_id: u32) -> Option<[u8; 32]> {
+            self.election_configs
+                .get(election_id)
+                .unwrap_or_default()
+                .allowlist_root
+        }
+        #[ink(message)]
+        pub fn is_proposals_locked(&self, election_id: u32) -> bool {
+            self.proposals_locked.get(election_id).unwrap_or_default()
+        }
+        #[ink(message)]
+        pub fn is_force_closed(&self, election_id: u32) -> bool {
+            self.force_closed.get(election_id).unwrap_or_default()
+        }
+        #[ink(message)]
+        pub fn is_finalized(&self, election_id: u32) -> bool {
+            self.finalized_results.contains(election_id)
+        }
+        /// Returns (winner, votes, quorum_met). `quorum_met` covers both the
+        /// weight quorum and `min_voters`; elections without either always
+        /// report it as met.
+        #[ink(message)]
+        pub fn get_final_outcome(&self, election_id: u32) -> Result<(Vec<u8>, u128, bool)> {
+            self.check_results_visible(&election_id)?;
+            let (winner, votes) = self._winner(election_id);
+            Ok((
+                winner,
+                votes,
+                self.quorum_met(&election_id) && self.is_voter_quorum_reached(election_id),
+            ))
+        }
+        /// Returns whether at least `min_voters` accounts have voted.
+        #[ink(message)]
+        pub fn is_voter_quorum_reached(&self, election_id: u32) -> bool {
+            let min_voters = self
+                .election_configs
+                .get(election_id)
+                .unwrap_or_default()
+                .min_voters;
+            self.get_voted_count(election_id) >= min_voters
+        }
+        /// Returns the outcome recorded at finalization, or `None` if the
+        /// election has not been finalized.
+        #[ink(message)]
+        pub fn get_election_outcome(&self, election_id: u32) -> Option<ElectionOutcome> {
+            self.outcomes.get(election_id)
+        }
+        #[ink(message)]
+        pub fn get_finalized_result(&self, election_id: u32) -> (Vec<u8>, u128) {
+            self.finalized_results.get(election_id).unwrap_or_default()
+        }
+        #[ink(message)]
+        pub fn is_account_registered(&self, election_id: u32, account: AccountId) -> bool {
+            self.voters.get((election_id, account)).is_some()
+        }
+        #[ink(message)]
+        pub fn get_result_election(&self, election_id: u32) -> Result<ProposalResults> {
+            self.check_results_visible(&election_id)?;
+            Ok(self._result_election(election_id))
+        }
+
+        /// Returns the weight a vote by `voter` would add to the tally right
+        /// now, after decay and the early bonus. Single-vote elections always
+        /// count the voter's full weight.
+        #[ink(message)]
+        pub fn effective_weight(&self, election_id: u32, voter: AccountId, weight: u128) -> u128 {
+            let weight = if self.is_single_vote(&election_id) {
+                self.get_voter_weigth(election_id, voter)
+            } else {
+                weight
+            };
+            self._effective_weight(&election_id, &weight)
+        }
+
+        /// Returns up to `MAX_PAGE_SIZE` results starting at position `start`
+        /// and emits them as a `ResultChunk` for indexers. It takes `&mut self`
+        /// so the metadata marks it as mutating: clients submit it as a
+        /// transaction instead of a dry run, which would drop the event.
+        #[ink(message)]
+        pub fn dump_results(
+            &mut self,
+            election_id: u32,
+            start: u32,
+            limit: u32,
+        ) -> Result<ProposalResults> {
+            self.check_id_existence(&election_id)?;
+            self.check_results_visible(&election_id)?;
+            let results: ProposalResults = self
+                ._result_election(election_id)
+                .into_iter()
+                .skip(start as usize)
+                .take(limit.min(MAX_PAGE_SIZE) as usize)
+                .collect();
+            Self::env().emit_event(ResultChunk {
+                election_id,
+                start,
+                results: results.clone(),
+            });
+            Ok(results)
+        }
+
+        fn _result_election(&self, election_id: u32) -> Vec<(Vec<u8>, u128)> {
+            let mut result = Vec::new();
+            for proposal in self.proposals_list.get(election_id).unwrap_or_default() {
+                let proposal_id = self
+                    .proposals_ids
+                    .get((election_id, &proposal))
+                    .unwrap_or_default();
+                result.push((
+                    proposal,
+                    self.vote_proposals
+                        .get((election_id, proposal_id))
+                        .unwrap_or_default(),
+                ));
+            }
+            result
+        }
+        #[ink(message)]
+        pub fn get_results_by_id(&self, election_id: u32) -> Result<Vec<(u32, u128)>> {
+            self.check_results_visible(&election_id)?;
+            Ok(self
+                .get_proposals_with_ids(election_id)
+                .into_iter()
+                .map(|(proposal_id, _)| {
+                    (
+                        proposal_id,
+                        self.vote_proposals
+                            .get((election_id, proposal_id))
+                            .unwrap_or_default(),
+                    )
+                })
+                .collect())
+        }
+        /// Blake2x256 hash of the SCALE encoded (proposal, votes) pairs in
+        /// proposal id order, so clients can check results they computed.
+        #[ink(message)]
+        pub fn get_results_hash(&self, election_id: u32) -> Result<[u8; 32]> {
+            self.check_results_visible(&election_id)?;
+            let encoded = scale::Encode::encode(&self._result_election(election_id));
+            Ok(Self::env().hash_bytes::<ink_env::hash::Blake2x256>(&encoded))
+        }
+
+        /// Returns the results of each known election among `ids`, skipping
+        /// elections whose results are hidden. Only the first `MAX_PAGE_SIZE`
+        /// ids are looked at.
+        #[ink(message)]
+        pub fn get_results_for_elections(&self, ids: Vec<u32>) -> Vec<(u32, ProposalResults)> {
+            ids.into_iter()
+                .take(MAX_PAGE_SIZE as usize)
+                .filter(|election_id| {
+                    self._election_id_exists(election_id)
+                        && self.check_results_visible(election_id).is_ok()
+                })
+                .map(|election_id| (election_id, self._result_election(election_id)))
+                .collect()
+        }
+        #[ink(message)]
+        pub fn get_votes_proposal(&self, election_id: u32, proposal: Vec<u8>) -> Result<u128> {
+            self.check_results_visible(&election_id)?;
+            Ok(self._votes_proposal(election_id, proposal))
+        }
+
+        fn _votes_proposal(&self, election_id: u32, proposal: Vec<u8>) -> u128 {
+            let proposal_id = self
+                .proposals_ids
+                .get((election_id, proposal))
+                .unwrap_or_default();
+            self.vote_proposals
+                .get((election_id, proposal_id))
+                .unwrap_or_default()
+        }
+
+        #[ink(message)]
+        pub fn get_winner(&self, election_id: u32) -> Result<(Vec<u8>, u128)> {
+            self.check_results_visible(&election_id)?;
+            Ok(self._winner(election_id))
+        }
+
+        fn _winner(&self, election_id: u32) -> (Vec<u8>, u128) {
+            if let Some(result) = self.finalized_results.get(election_id) {
+                return result;
+            }
+            if self.is_motion(election_id) {
+                let (for_weight, against_weight, passed) = self._motion_result(election_id);
+                return if passed {
+                    (MOTION_FOR.to_vec(), for_weight - against_weight)
+                } else if against_weight > for_weight {
+                    (MOTION_AGAINST.to_vec(), against_weight - for_weight)
+                } else {
+                    (Vec::new(), 0)
+                };
+            }
+            let tie_break = self
+                .election_configs
+                .get(election_id)
+                .unwrap_or_default()
+                .tie_break;
+            let earliest_first_vote = tie_break == TieBreak::EarliestFirstVote;
+            let mut winner = Vec::new();
+            let mut winner_first_vote = 0;
+            let mut max_votes = 0;
+            let mut proposal_id;
+            let mut vote_proposal;
+            for proposal in self.proposals_list.get(election_id).unwrap_or_default() {
+                proposal_id = self
+                    .proposals_ids
+                    .get((election_id, &proposal))
+                    .unwrap_or_default();
+                vote_proposal = self
+                    .vote_proposals
+                    .get((election_id, proposal_id))
+                    .unwrap_or_default();
+                let first_vote = self
+                    .proposal_first_vote_ts
+                    .get((election_id, proposal_id))
+                    .unwrap_or_default();
+                let wins_tie = earliest_first_vote
+                    && vote_proposal == max_votes
+                    && max_votes > 0
+                    && first_vote < winner_first_vote;
+                if vote_proposal > max_votes || wins_tie {
+                    winner = proposal;
+                    winner_first_vote = first_vote;
+                    max_votes = vote_proposal;
+                }
+            }
+            if tie_break == TieBreak::Random && max_votes > 0 {
+                let tied: Vec<Vec<u8>> = self
+                    ._result_election(election_id)
+                    .into_iter()
+                    .filter(|(_, votes)| *votes == max_votes)
+                    .map(|(proposal, _)| proposal)
+                    .collect();
+                let (seed, _) = Self::env().random(&election_id.to_le_bytes());
+                winner = Self::random_pick(&tied, seed.as_ref());
+            }
+            (winner, max_votes)
+        }
+
+        #[ink(message)]
+        pub fn is_motion(&self, election_id: u32) -> bool {
+            self.motions.get(election_id).unwrap_or_default()
+        }
+
+        #[ink(message)]
+        pub fn get_motion_result(&self, election_id: u32) -> Result<(u128, u128, bool)> {
+            self.check_results_visible(&election_id)?;
+            Ok(self._motion_result(election_id))
+        }
+
+        fn _motion_result(&self, election_id: u32) -> (u128, u128, bool) {
+            if !self.is_motion(election_id) {
+                return (0, 0, false);
+            }
+            let for_weight = self._votes_proposal(election_id, MOTION_FOR.to_vec());
+            let against_weight = self._votes_proposal(election_id, MOTION_AGAINST.to_vec());
+            (for_weight, against_weight, for_weight > against_weight)
+        }
+
+        #[ink(message)]
+        pub fn get_voter_count(&self, election_id: u32) -> u32 {
+            self.voter_count.get(election_id).unwrap_or_default()
+        }
+
+        /// Returns how many more voters can register, or `u32::MAX` when the
+        /// election has no voter limit.
+        #[ink(message)]
+        pub fn get_remaining_slots(&self, election_id: u32) -> u32 {
+            let max_voters = self
+                .election_configs
+                .get(election_id)
+                .unwrap_or_default()
+                .max_voters;
+            if max_voters == 0 {
+                return u32::MAX;
+            }
+            max_voters.saturating_sub(self.get_voter_count(election_id))
+        }
+
+        #[ink(message)]
+        pub fn get_voted_count(&self, election_id: u32) -> u32 {
+            self.voted_count.get(election_id).unwrap_or_default()
+        }
+
+        /// Total weight held by registered voters when the election was last
+        /// opened. Voters registered implicitly by voting in an election without
+        /// required registration are not included.
+        #[ink(message)]
+        pub fn get_eligible_weight(&self, election_id: u32) -> u128 {
+            self.eligible_weight.get(election_id).unwrap_or_default()
+        }
+
+        #[ink(message)]
+        pub fn get_abstentions(&self, election_id: u32) -> u32 {
+            self.get_voter_count(election_id)
+                .saturating_sub(self.get_voted_count(election_id))
+        }
+
+        /// Share of registered voters who have voted, in basis points.
+        #[ink(message)]
+        pub fn get_participation_rate(&self, election_id: u32) -> u32 {
+            let voter_count = self.get_voter_count(election_id) as u64;
+            if voter_count == 0 {
+                return 0;
+            }
+            (self.get_voted_count(election_id) as u64 * 10_000 / voter_count) as u32
+        }
+
+        /// Sums the weight voters still hold and the votes already cast. Voting
+        /// and delegating only move weight around, so this should equal the
+        /// total weight granted to voters; decayed and boosted votes are the
+        /// exception.
+        #[ink(message)]
+        pub fn audit_weight(&self, election_id: u32) -> u128 {
+            self.total_voter_weight(&election_id)
+                .saturating_add(self.total_votes(&election_id))
+        }
+
+        /// Share of each proposal in basis points of the configured total
+        /// supply, or of the votes cast when no supply was set.
+        #[ink(message)]
+        pub fn get_results_vs_supply(&self, election_id: u32) -> Result<Vec<(Vec<u8>, u32)>> {
+            self.check_results_visible(&election_id)?;
+            let total_supply = self
+                .election_configs
+                .get(election_id)
+                .unwrap_or_default()
+                .total_supply;
+            let denominator = if total_supply == 0 {
+                self.total_votes(&election_id)
+            } else {
+                total_supply
+            };
+            Ok(self
+                ._result_election(election_id)
+                .into_iter()
+                .map(|(proposal, votes)| {
+                    let share = votes
+                        .saturating_mul(10_000)
+                        .checked_div(denominator)
+                        .unwrap_or_default()
+                        .min(u32::MAX as u128) as u32;
+                    (proposal, share)
+                })
+                .collect())
+        }
+
+        #[ink(message)]
+        pub fn get_runner_up(&self, election_id: u32) -> Result<(Vec<u8>, u128)> {
+            self.check_results_visible(&election_id)?;
+            Ok(self._runner_up(election_id))
+        }
+
+        fn _runner_up(&self, election_id: u32) -> (Vec<u8>, u128) {
+            let winner = self._winner(election_id).0;
+            let mut runner_up = Vec::new();
+            let mut max_votes = 0;
+            for (proposal, votes) in self._result_election(election_id) {
+                if proposal != winner && votes > max_votes {
+                    runner_up = proposal;
+                    max_votes = votes;
+                }
+            }
+            (runner_up, max_votes)
+        }
+
+        /// Returns how far the winner is ahead of the runner-up, or 0 when
+        /// first place is tied.
+        #[ink(message)]
+        pub fn get_winning_margin(&self, election_id: u32) -> Result<u128> {
+            self.check_results_visible(&election_id)?;
+            Ok(self._winning_margin(election_id))
+        }
+
+        fn _winning_margin(&self, election_id: u32) -> u128 {
+            let (_, winner_votes) = self._winner(election_id);
+            if self.is_motion(election_id) {
+                return winner_votes;
+            }
+            winner_votes - self._runner_up(election_id).1
+        }
+
+        /// Returns (proposal, votes, rank) for every proposal, most votes first.
+        /// Tied proposals share a rank and keep their listed order.
+        #[ink(message)]
+        pub fn get_distribution(&self, election_id: u32) -> Result<Vec<(Vec<u8>, u128, u32)>> {
+            self.check_results_visible(&election_id)?;
+            Ok(self._distribution(election_id))
+        }
+
+        fn _distribution(&self, election_id: u32) -> Vec<(Vec<u8>, u128, u32)> {
+            let mut results = self._result_election(election_id);
+            results.sort_by_key(|(_, votes)| core::cmp::Reverse(*votes));
+            let mut distribution: Vec<(Vec<u8>, u128, u32)> = Vec::new();
+            for (i, (proposal, votes)) in results.into_iter().enumerate() {
+                let rank = match distribution.last() {
+                    Some((_, previous_votes, previous_rank)) if *previous_votes == votes => {
+                        *previous_rank
+                    }
+                    _ => i as u32 + 1,
+                };
+                distribution.push((proposal, votes, rank));
+            }
+            distribution
+        }
+
+        /// Returns the `n` proposals with the most votes, most votes first.
+        #[ink(message)]
+        pub fn get_top_proposals(&self, election_id: u32, n: u32) -> Result<ProposalResults> {
+            self.check_results_visible(&election_id)?;
+            Ok(self
+                ._distribution(election_id)
+                .into_iter()
+                .take(n as usize)
+                .map(|(proposal, votes, _)| (proposal, votes))
+                .collect())
+        }
+
+        /// Returns the 1-based rank of the proposal by votes, where tied
+        /// proposals share a rank, or 0 for an unknown proposal.
+        #[ink(message)]
+        pub fn get_proposal_rank(&self, election_id: u32, proposal: Vec<u8>) -> Result<u32> {
+            self.check_results_visible(&election_id)?;
+            let results = self._result_election(election_id);
+            Ok(match results.iter().find(|(name, _)| *name == proposal) {
+                Some((_, votes)) => {
+                    results.iter().filter(|(_, other)| other > votes).count() as u32 + 1
+                }
+                None => 0,
+            })
+        }
+
+        #[ink(message)]
+        pub fn get_voter_weigth(&self, election_id: u32, voter: AccountId) -> u128 {
+            self.voters.get((election_id, voter)).unwrap_or_default().0
+        }
+
+        /// Sums the voter's remaining weight over every election they are
+        /// registered in.
+        #[ink(message)]
+        pub fn get_total_remaining_weight(&self, voter: AccountId) -> u128 {
+            self.voter_elections
+                .get(voter)
+                .unwrap_or_default()
+                .iter()
+                .map(|election_id| self.get_voter_weigth(*election_id, voter))
+                .fold(0, u128::saturating_add)
+        }
+
+        /// Returns the weight other voters delegated to this account. It is
+        /// already included in `get_voter_weigth`.
+        #[ink(message)]
+        pub fn get_delegated_weight(&self, election_id: u32, voter: AccountId) -> u128 {
+            self.delegated_in
+                .get((election_id, voter))
+                .unwrap_or_default()
+        }
+
+        /// Returns the proposal the voter backed, or an empty name if they have not
+        /// voted. A voter who spread their weight over several proposals gets the
+        /// first one they voted for.
+        #[ink(message)]
+        pub fn get_voter_choice(&self, election_id: u32, voter: AccountId) -> Vec<u8> {
+            match self
+                .voter_choices
+                .get((election_id, voter))
+                .unwrap_or_default()
+                .first()
+            {
+                Some((proposal_id, _, _)) => {
+                    self.get_proposal_by_index(election_id, proposal_id - 1)
+                }
+                None => Vec::new(),
+            }
+        }
+
+        #[ink(message)]
+        pub fn get_my_weight(&self, election_id: u32) -> u128 {
+            self.get_voter_weigth(election_id, Self::env().caller())
+        }
+
+        /// Returns (voted, proposal, weight_spent) for the caller. The proposal
+        /// is the first one they voted for and the weight is the total they
+        /// spent across all of their votes, before any decay or bonus.
+        #[ink(message)]
+        pub fn get_my_receipt(&self, election_id: u32) -> (bool, Vec<u8>, u128) {
+            let voter = Self::env().caller();
+            let choices = self
+                .voter_choices
+                .get((election_id, voter))
+                .unwrap_or_default();
+            if choices.is_empty() {
+                return (false, Vec::new(), 0);
+            }
+            let weight_spent = choices.iter().map(|(_, _, spent)| spent).sum();
+            (
+                true,
+                self.get_voter_choice(election_id, voter),
+                weight_spent,
+            )
+        }
+
+        #[ink(message)]
+        pub fn has_voter_voted(&self, election_id: u32, voter: AccountId) -> bool {
+            self.voters.get((election_id, voter)).unwrap_or_default().1
+        }
+
+        fn subtract_weight(&mut self, election_id: &u32, voter: &AccountId, weight: &u128) {
+            let voter_weight = self.voters.get((election_id, voter)).unwrap().0;
+            if voter_weight - weight == 0 {
+                self.voters.insert((&election_id, &voter), &(0, true));
+            } else {
+                self.voters
+                    .insert((&election_id, &voter), &(voter_weight - weight, false));
+            }
+        }
+        fn verify_proof(&self, root: &[u8; 32], account: &AccountId, proof: &[[u8; 32]]) -> bool {
+            let mut node = Self::env().hash_bytes::<ink_env::hash::Blake2x256>(account.as_ref());
+            for sibling in proof {
+                let mut pair = [0u8; 64];
+                let (first, second) = if node <= *sibling {
+                    (&node, sibling)
+                } else {
+                    (sibling, &node)
+                };
+                pair[..32].copy_from_slice(first);
+                pair[32..].copy_from_slice(second);
+                node = Self::env().hash_bytes::<ink_env::hash::Blake2x256>(&pair);
+            }
+            &node == root
+        }
+        fn register_voter(&mut self, voter: &AccountId, election_id: &u32) -> Result<()> {
+            if self.get_remaining_slots(*election_id) == 0 {
+                return Err(Error::VoterLimitReached);
+            }
+            self.voters.insert((election_id, voter), &(1, false));
+            self.voter_count
+                .insert(election_id, &(self.get_voter_count(*election_id) + 1));
+            let mut voter_list = self.voter_list.get(election_id).unwrap_or_default();
+            voter_list.push(*voter);
+            self.voter_list.insert(election_id, &voter_list);
+            let mut voter_elections = self.voter_elections.get(voter).unwrap_or_default();
+            voter_elections.push(*election_id);
+            self.voter_elections.insert(voter, &voter_elections);
+            Ok(())
+        }
+        fn total_voter_weight(&self, election_id: &u32) -> u128 {
+            self.voter_list
+                .get(election_id)
+                .unwrap_or_default()
+                .iter()
+                .map(|voter| self.voters.get((election_id, voter)).unwrap_or_default().0)
+                .fold(0, u128::saturating_add)
+        }
+        fn election_name(&self, election_id: &u32) -> Vec<u8> {
+            self.elections_list
+                .iter()
+                .find(|name| self.elections_ids.get(name) == Some(*election_id))
+                .cloned()
+                .unwrap_or_default()
+        }
+        fn _election_name_exists(&self, name: &Vec<u8>) -> bool {
+            self.elections_ids.get(name).is_some()
+        }
+
+        fn _is_election_open(&self, election_id: &u32) -> bool {
+            self.elections.get(election_id).unwrap_or_default().3 == ElectionState::ElectionOpen
+        }
+
+        fn _is_registration_open(&self, election_id: &u32) -> bool {
+            self.elections.get(election_id).unwrap_or_default().2
+                == RegistrationState::RegistrationOpen
+        }
+
+        fn delegate(
+            &mut self,
+            election_id: &u32,
+            delegate: &AccountId,
+            delegator_address: &AccountId,
+            weight: &u128,
+        ) -> Result<()> {
+            let weight_delegate = self.voters.get((election_id, delegate)).unwrap().0;
+            let new_weight = weight_delegate
+                .checked_add(*weight)
+                .ok_or(Error::WeightOverflow)?;
+            let new_delegated = self
+                .delegated_in
+                .get((election_id, delegate))
+                .unwrap_or_default()
+                .checked_add(*weight)
+                .ok_or(Error::WeightOverflow)?;
+            self.voters
+                .insert((election_id, delegate), &(new_weight, false));
+            self.delegated_in
+                .insert((election_id, delegate), &new_delegated);
+            self.subtract_weight(election_id, delegator_address, weight);
+            Ok(())
+        }
+
+        fn set_owner(&mut self, election_id: &u32, new_owner: &AccountId) {
+            let mut election = self.elections.get(election_id).unwrap();
+            let mut old_owner_elections = self.owner_elections.get(election.0).unwrap_or_default();
+            old_owner_elections.retain(|id| id != election_id);
+            self.owner_elections
+                .insert(election.0, &old_owner_elections);
+            let mut new_owner_elections = self.owner_elections.get(new_owner).unwrap_or_default();
+            new_owner_elections.push(*election_id);
+            self.owner_elections.insert(new_owner, &new_owner_elections);
+            election.0 = *new_owner;
+            self.elections.insert(election_id, &election);
+            self.pending_owners.remove(election_id);
+        }
         fn is_owner(&self, account: &AccountId, election_id: &u32) -> bool {
             account == &self.elections.get(election_id).unwrap_or_default().0
         }
@@ -444,18 +2274,144 @@ mod ink_voting_dapp {
             }
         }
         fn _election_id_exists(&self, id: &u32) -> bool {
-            self.elections.get(id) != None
+            self.elections.get(id).is_some()
         }
-        fn check_sufficient_proposals(&self, proposals: &Vec<Vec<u8>>) -> Result<()> {
-            if proposals.len() == 0 {
+        fn check_sufficient_proposals(&self, proposals: &[Vec<u8>]) -> Result<()> {
+            if proposals.is_empty() {
                 Err(Error::InsufficientProposals)
+            } else if proposals.len() > MAX_PROPOSALS {
+                Err(Error::TooManyProposals)
             } else {
                 Ok(())
             }
         }
-        fn insert_proposal(&mut self, election_id: &u32, proposal: &Vec<u8>, proposal_id: &u32) {
-            self.vote_proposals
-                .insert((election_id, (proposal_id + 1)), &0);
+        fn allows_writeins(&self, election_id: &u32) -> bool {
+            self.election_configs
+                .get(election_id)
+                .unwrap_or_default()
+                .allow_writeins
+        }
+        fn emit_voted(
+            &self,
+            election_id: u32,
+            voter: AccountId,
+            proposal: Vec<u8>,
+            weight: u128,
+            new_total: u128,
+        ) {
+            let config = self.election_configs.get(election_id).unwrap_or_default();
+            // Summing proposal and weight over events would rebuild the tally.
+            let (proposal, weight, new_total) = if config.hide_results {
+                (Vec::new(), 0, 0)
+            } else {
+                (proposal, weight, new_total)
+            };
+            if !config.anonymize {
+                Self::env().emit_event(Voted {
+                    voter,
+                    proposal,
+                    weight,
+                    new_total,
+                });
+                return;
+            }
+            Self::env().emit_event(AnonymousVoted {
+                election_id,
+                voter_hash: Self::voter_hash(&voter, &config.salt),
+                proposal,
+                weight,
+                new_total,
+            });
+        }
+        fn emit_vote_revoked(&self, election_id: u32, voter: AccountId, weight: u128) {
+            let config = self.election_configs.get(election_id).unwrap_or_default();
+            if config.anonymize {
+                Self::env().emit_event(AnonymousVoteRevoked {
+                    election_id,
+                    voter_hash: Self::voter_hash(&voter, &config.salt),
+                    weight,
+                });
+            } else {
+                Self::env().emit_event(VoteRevoked {
+                    election_id,
+                    voter,
+                    weight,
+                });
+            }
+        }
+        fn random_pick(tied: &[Vec<u8>], seed: &[u8]) -> Vec<u8> {
+            let mut index_bytes = [0u8; 4];
+            index_bytes.copy_from_slice(&seed[..4]);
+            tied[u32::from_le_bytes(index_bytes) as usize % tied.len()].clone()
+        }
+        fn voter_hash(voter: &AccountId, salt: &[u8; 32]) -> [u8; 32] {
+            let mut input = [0u8; 64];
+            input[..32].copy_from_slice(voter.as_ref());
+            input[32..].copy_from_slice(salt);
+            Self::env().hash_bytes::<ink_env::hash::Blake2x256>(&input)
+        }
+        fn mark_voting_started(&mut self, election_id: &u32) {
+            if !self.first_vote_done.get(election_id).unwrap_or_default() {
+                self.first_vote_done.insert(election_id, &true);
+                Self::env().emit_event(VotingStarted {
+                    election_id: *election_id,
+                    date: Self::env().block_timestamp(),
+                });
+            }
+        }
+        fn total_votes(&self, election_id: &u32) -> u128 {
+            self._result_election(*election_id)
+                .iter()
+                .fold(0u128, |total, (_, votes)| total.saturating_add(*votes))
+        }
+        fn quorum_met(&self, election_id: &u32) -> bool {
+            let quorum = self
+                .election_configs
+                .get(election_id)
+                .unwrap_or_default()
+                .quorum;
+            self.total_votes(election_id) >= quorum
+        }
+        fn compute_outcome(&self, election_id: &u32) -> ElectionOutcome {
+            if self.total_votes(election_id) == 0 {
+                ElectionOutcome::NoVotes
+            } else if !self.quorum_met(election_id) || !self.is_voter_quorum_reached(*election_id) {
+                ElectionOutcome::FailedQuorum
+            } else if self._winning_margin(*election_id) == 0 {
+                ElectionOutcome::Tied
+            } else {
+                ElectionOutcome::Passed
+            }
+        }
+        fn is_strict(&self, election_id: &u32) -> bool {
+            self.election_configs
+                .get(election_id)
+                .unwrap_or_default()
+                .strict
+        }
+        fn is_single_vote(&self, election_id: &u32) -> bool {
+            self.election_configs
+                .get(election_id)
+                .unwrap_or_default()
+                .single_vote
+        }
+        fn add_proposal_to_list(&mut self, election_id: &u32, proposal: &Vec<u8>) -> Result<()> {
+            let mut proposals = self.proposals_list.get(election_id).unwrap_or_default();
+            if proposals.len() >= MAX_PROPOSALS {
+                return Err(Error::TooManyProposals);
+            }
+            self.insert_proposal(election_id, proposal, &(proposals.len() as u32));
+            proposals.push(proposal.clone());
+            self.proposals_list.insert(election_id, &proposals);
+            if let Some(mut order) = self.proposal_order.get(election_id) {
+                order.push(proposals.len() as u32);
+                self.proposal_order.insert(election_id, &order);
+            }
+            Ok(())
+        }
+        fn insert_proposal(&mut self, election_id: &u32, proposal: &Vec<u8>, proposal_id: &u32) {
+            self.vote_proposals
+                .insert((election_id, (proposal_id + 1)), &0);
             self.proposals_ids
                 .insert((election_id, proposal), &(proposal_id + 1));
         }
@@ -468,7 +2424,7 @@ mod ink_voting_dapp {
             proposals: &Vec<Vec<u8>>,
         ) {
             self.elections.insert(
-                &election_id,
+                election_id,
                 &(
                     owner,
                     required_registration,
@@ -476,369 +2432,4137 @@ mod ink_voting_dapp {
                     ElectionState::ElectionClosed,
                 ),
             );
+            self.created_at
+                .insert(election_id, &Self::env().block_timestamp());
+            let mut owner_elections = self.owner_elections.get(owner).unwrap_or_default();
+            owner_elections.push(*election_id);
+            self.owner_elections.insert(owner, &owner_elections);
             self.elections_ids.insert(name, election_id);
             self.elections_list.push(name.to_vec());
             for i in 0..proposals.len() {
-                self.insert_proposal(&election_id, proposals.get(i).unwrap(), &(i as u32));
+                self.insert_proposal(election_id, proposals.get(i).unwrap(), &(i as u32));
+            }
+            self.proposals_list.insert(election_id, proposals);
+        }
+        fn check_voting_started(&self, election_id: &u32) -> Result<()> {
+            let voting_delay = self
+                .election_configs
+                .get(election_id)
+                .unwrap_or_default()
+                .voting_delay;
+            let opened_at = self.opened_at.get(election_id).unwrap_or_default();
+            if Self::env().block_timestamp() < opened_at.saturating_add(voting_delay) {
+                Err(Error::VotingNotStarted)
+            } else {
+                Ok(())
+            }
+        }
+        fn check_election_open(&self, election_id: &u32) -> Result<()> {
+            if !self._is_election_open(election_id) {
+                Err(Error::ElectionClosed)
+            } else {
+                Ok(())
+            }
+        }
+        fn check_proposals_unlocked(&self, election_id: &u32) -> Result<()> {
+            if self.is_proposals_locked(*election_id) {
+                Err(Error::ProposalsLocked)
+            } else {
+                Ok(())
+            }
+        }
+        fn check_not_finalized(&self, election_id: &u32) -> Result<()> {
+            if self.is_finalized(*election_id) {
+                Err(Error::ElectionFinalized)
+            } else {
+                Ok(())
+            }
+        }
+        fn check_registration_open(&self, election_id: &u32) -> Result<()> {
+            match self.get_phase(*election_id) {
+                Phase::Finalized => return Err(Error::ElectionFinalized),
+                Phase::Closed => return Err(Error::ElectionClosed),
+                _ => {}
+            }
+            let deadline = self.get_registration_deadline(*election_id);
+            if !self._is_registration_open(election_id)
+                || (deadline != 0 && Self::env().block_timestamp() > deadline)
+            {
+                Err(Error::RegistrationClosed)
+            } else {
+                Ok(())
+            }
+        }
+        fn is_voter_registered(&self, election_id: &u32, voter_address: &AccountId) -> bool {
+            self.voters.get((election_id, voter_address)).is_some()
+        }
+
+        fn check_voter_registered(
+            &self,
+            election_id: &u32,
+            voter_address: &AccountId,
+        ) -> Result<()> {
+            if !self.is_voter_registered(election_id, voter_address) {
+                Err(Error::VoterNotRegistred)
+            } else {
+                Ok(())
+            }
+        }
+        fn check_proposal_valid(&self, election_id: &u32, proposal: &Vec<u8>) -> Result<()> {
+            if self.proposals_ids.get((election_id, proposal)).is_none() {
+                Err(Error::InvalidProposal)
+            } else {
+                Ok(())
+            }
+        }
+        fn check_proposal_cap(
+            &self,
+            election_id: &u32,
+            proposal: &Vec<u8>,
+            weight: &u128,
+        ) -> Result<()> {
+            let proposal_id = self.proposals_ids.get((election_id, proposal)).unwrap();
+            let cap = self
+                .proposal_caps
+                .get((election_id, proposal_id))
+                .unwrap_or_default();
+            let vote_proposal = self.vote_proposals.get((election_id, proposal_id)).unwrap();
+            let counted_weight = self._effective_weight(election_id, weight);
+            if cap != 0 && vote_proposal.saturating_add(counted_weight) > cap {
+                Err(Error::ProposalCapReached)
+            } else {
+                Ok(())
+            }
+        }
+        /// Read-only checks shared by `can_vote` and `cast_vote`. Returns the
+        /// weight the vote would be cast with: the full weight in single-vote
+        /// elections.
+        fn check_can_cast(
+            &self,
+            election_id: &u32,
+            voter: &AccountId,
+            weight: u128,
+        ) -> Result<u128> {
+            self.ensure_version()?;
+            self.check_id_existence(election_id)?;
+            self.check_not_finalized(election_id)?;
+            self.check_election_open(election_id)?;
+            self.check_voting_started(election_id)?;
+            let (voter_weight, voter_has_voted) = if self.is_voter_registered(election_id, voter) {
+                self.voters.get((election_id, voter)).unwrap()
+            } else if self.elections.get(election_id).unwrap().1 || self.is_strict(election_id) {
+                return Err(Error::VoterNotRegistred);
+            } else if self.get_remaining_slots(*election_id) == 0 {
+                return Err(Error::VoterLimitReached);
+            } else {
+                (1, false)
+            };
+            let weight = if self.is_single_vote(election_id) {
+                if weight != 0 && weight != voter_weight {
+                    return Err(Error::WeightMustMatchFull);
+                }
+                voter_weight
+            } else {
+                weight
+            };
+            self.check_max_vote_weight(&weight)?;
+            if voter_has_voted {
+                Err(Error::VoterHasAlreadyVoted)
+            } else if voter_weight < weight {
+                Err(Error::VoterHasNotSoMuchWeight)
+            } else {
+                Ok(weight)
+            }
+        }
+        fn check_voter_can_vote(
+            &self,
+            election_id: &u32,
+            voter_address: &AccountId,
+            weight: &u128,
+        ) -> Result<()> {
+            let (voter_weight, voter_has_voted) =
+                self.voters.get((election_id, voter_address)).unwrap();
+            if voter_has_voted {
+                Err(Error::VoterHasAlreadyVoted)
+            } else if &voter_weight < weight {
+                Err(Error::VoterHasNotSoMuchWeight)
+            } else {
+                Ok(())
+            }
+        }
+        fn _vote(
+            &mut self,
+            election_id: &u32,
+            proposal: &Vec<u8>,
+            voter_address: &AccountId,
+            weight: &u128,
+        ) -> Result<u128> {
+            let proposal_id = self.proposals_ids.get((election_id, proposal)).unwrap();
+            let vote_proposal = self.vote_proposals.get((election_id, proposal_id)).unwrap();
+            let counted_weight = self._effective_weight(election_id, weight);
+            let new_total = vote_proposal
+                .checked_add(counted_weight)
+                .ok_or(Error::TallyOverflow)?;
+            self.vote_proposals
+                .insert((election_id, proposal_id), &new_total);
+            if counted_weight > 0
+                && !self
+                    .proposal_first_vote_ts
+                    .contains((election_id, proposal_id))
+            {
+                self.proposal_first_vote_ts
+                    .insert((election_id, proposal_id), &Self::env().block_timestamp());
+            }
+            self.subtract_weight(election_id, voter_address, weight);
+            let mut choices = self
+                .voter_choices
+                .get((election_id, voter_address))
+                .unwrap_or_default();
+            if choices.is_empty() {
+                self.voted_count
+                    .insert(election_id, &(self.get_voted_count(*election_id) + 1));
+            }
+            match choices.iter_mut().find(|(id, _, _)| *id == proposal_id) {
+                Some(choice) => {
+                    choice.1 += counted_weight;
+                    choice.2 += weight;
+                }
+                None => choices.push((proposal_id, counted_weight, *weight)),
+            }
+            self.voter_choices
+                .insert((election_id, voter_address), &choices);
+            Ok(new_total)
+        }
+        fn _effective_weight(&self, election_id: &u32, weight: &u128) -> u128 {
+            let config = self.election_configs.get(election_id).unwrap_or_default();
+            let opened_at = self.opened_at.get(election_id).unwrap_or_default();
+            let elapsed = Self::env().block_timestamp().saturating_sub(opened_at);
+            let mut effective = *weight;
+            if config.decay && config.voting_period != 0 {
+                let period = config.voting_period as u128;
+                let remaining = config.voting_period.saturating_sub(elapsed) as u128;
+                // Split the product so it cannot overflow.
+                effective =
+                    effective / period * remaining + effective % period * remaining / period;
+            }
+            if elapsed < config.early_window {
+                let multiplier = 10_000 + config.early_bonus_bps as u128;
+                effective = (effective / 10_000)
+                    .saturating_mul(multiplier)
+                    .saturating_add(effective % 10_000 * multiplier / 10_000);
+            }
+            effective
+        }
+        fn _revoke_vote(
+            &mut self,
+            election_id: &u32,
+            voter_address: &AccountId,
+            choices: &[(u32, u128, u128)],
+        ) -> u128 {
+            let mut revoked_weight = 0;
+            for (proposal_id, counted_weight, spent_weight) in choices {
+                let vote_proposal = self.vote_proposals.get((election_id, proposal_id)).unwrap();
+                self.vote_proposals.insert(
+                    (election_id, proposal_id),
+                    &(vote_proposal - counted_weight),
+                );
+                revoked_weight += spent_weight;
+            }
+            let voter_weight = self.voters.get((election_id, voter_address)).unwrap().0;
+            self.voters.insert(
+                (election_id, voter_address),
+                &(voter_weight + revoked_weight, false),
+            );
+            self.voter_choices.remove((election_id, voter_address));
+            self.voted_count
+                .insert(election_id, &(self.get_voted_count(*election_id) - 1));
+            revoked_weight
+        }
+        fn only_owner(&self, election_id: &u32, address: &AccountId) -> Result<()> {
+            if !self.is_owner(address, election_id) {
+                Err(Error::OnlyOwner)
+            } else {
+                Ok(())
+            }
+        }
+        fn only_admin(&self, address: &AccountId) -> Result<()> {
+            if address != &self.admin {
+                Err(Error::OnlyAdmin)
+            } else {
+                Ok(())
+            }
+        }
+        /// Ids of up to `MAX_PAGE_SIZE` elections starting at position `start`.
+        fn id_page(&self, start: u32, limit: u32) -> core::ops::Range<u32> {
+            let first_id = start.saturating_add(1);
+            let last_id = first_id
+                .saturating_add(limit.min(MAX_PAGE_SIZE))
+                .min(self.election_nonce);
+            first_id..last_id
+        }
+        fn check_results_visible(&self, election_id: &u32) -> Result<()> {
+            let hide_results = self
+                .election_configs
+                .get(election_id)
+                .unwrap_or_default()
+                .hide_results;
+            if hide_results
+                && self._is_election_open(election_id)
+                && !self.is_owner(&Self::env().caller(), election_id)
+            {
+                Err(Error::ResultsHidden)
+            } else {
+                Ok(())
+            }
+        }
+        fn check_creation_limit(&self, owner: &AccountId) -> Result<()> {
+            if self.max_elections_per_account != 0
+                && self.get_owner_election_count(*owner) >= self.max_elections_per_account
+            {
+                Err(Error::CreationLimitReached)
+            } else {
+                Ok(())
+            }
+        }
+        fn check_max_vote_weight(&self, weight: &u128) -> Result<()> {
+            if self.max_vote_weight != 0 && *weight > self.max_vote_weight {
+                Err(Error::WeightTooLarge)
+            } else {
+                Ok(())
+            }
+        }
+        fn ensure_version(&self) -> Result<()> {
+            if self.storage_version != STORAGE_VERSION {
+                Err(Error::VersionMismatch)
+            } else {
+                Ok(())
+            }
+        }
+        fn check_not_paused(&self) -> Result<()> {
+            if self.paused {
+                Err(Error::ContractPaused)
+            } else {
+                Ok(())
+            }
+        }
+        fn _close_election(&mut self, election_id: &u32) {
+            let mut election = self.elections.get(election_id).unwrap();
+            election.3 = ElectionState::ElectionClosed;
+            self.elections.insert(election_id, &election);
+            Self::env().emit_event(CloseElection {
+                election_id: *election_id,
+                date: Self::env().block_timestamp(),
+            });
+        }
+        fn check_if_registration_needed(
+            &mut self,
+            election_id: &u32,
+            voter_address: &AccountId,
+        ) -> Result<()> {
+            if self.elections.get(election_id).unwrap().1 || self.is_strict(election_id) {
+                self.check_voter_registered(election_id, voter_address)?;
+            } else {
+                if !self.is_voter_registered(election_id, voter_address) {
+                    self.register_voter(voter_address, election_id)?;
+                }
+            }
+            Ok(())
+        }
+        fn check_double_election(&self, name: &Vec<u8>) -> Result<()> {
+            if self._election_name_exists(name) {
+                Err(Error::ElectionNotValid)
+            } else {
+                Ok(())
+            }
+        }
+    }
+
+    /// Unit tests in Rust are normally defined within such a `#[cfg(test)]`
+    /// module and test functions are marked with a `#[test]` attribute.
+    /// The below code is technically just normal Rust code.
+    #[cfg(test)]
+    #[allow(clippy::bool_assert_comparison)]
+    mod tests {
+        /// Imports all the definitions from the outer scope so we can use them here.
+        use super::*;
+
+        /// Imports `ink_lang` so we can use `#[ink::test]`.
+        use ink_lang as ink;
+
+        /// We test if the default constructor does its job.
+        #[ink::test]
+        fn new_works() {
+            let ink_voting_dapp = InkVotingDapp::new();
+            assert_eq!(ink_voting_dapp.get_number_elections(), 0);
+        }
+
+        type Event = <InkVotingDapp as ink::reflect::ContractEventBase>::Type;
+
+        fn decode_event(event: &ink_env::test::EmittedEvent) -> Event {
+            <Event as scale::Decode>::decode(&mut &event.data[..]).unwrap()
+        }
+        fn to_ut8(string: &str) -> Vec<u8> {
+            string.as_bytes().to_vec()
+        }
+        fn initialize_and_create_election(require_registration: bool) -> Result<InkVotingDapp> {
+            let mut ink_voting_dapp = InkVotingDapp::new();
+            ink_voting_dapp.create_election(
+                to_ut8("firstelection"),
+                require_registration,
+                vec![to_ut8("firstproposal"), to_ut8("secondproposal")],
+            )?;
+            Ok(ink_voting_dapp)
+        }
+
+        /// We test a simple use case of our contract.
+        #[ink::test]
+        fn create_election_works() {
+            let mut ink_voting_dapp = InkVotingDapp::new();
+            //let accounts = ink_env::test::default_accounts::<ink_env::DefaultEnvironment>();
+            assert_eq!(
+                ink_voting_dapp.create_election(
+                    to_ut8("firstelection"),
+                    false,
+                    vec![to_ut8("firstproposal"), to_ut8("secondproposal")]
+                ),
+                Ok(())
+            );
+            assert_eq!(
+                ink_voting_dapp.create_election(
+                    to_ut8("firstelection"),
+                    false,
+                    vec![to_ut8("firstproposal")]
+                ),
+                Err(Error::ElectionNotValid)
+            );
+            assert_eq!(
+                ink_voting_dapp.create_election(to_ut8("secondelection"), false, vec![]),
+                Err(Error::InsufficientProposals)
+            );
+            assert_eq!(ink_voting_dapp.get_number_elections(), 1);
+            let events = ink_env::test::recorded_events().collect::<Vec<_>>();
+            assert_eq!(events.len(), 1);
+            if let Event::ElectionCreated(ElectionCreated { proposal_count, .. }) =
+                decode_event(&events[0])
+            {
+                assert_eq!(proposal_count, 2);
+            } else {
+                panic!("expected an ElectionCreated event");
+            }
+            assert_eq!(
+                ink_voting_dapp.get_proposal_for_election(to_ut8("firstelection")),
+                vec![to_ut8("firstproposal"), to_ut8("secondproposal")]
+            );
+            assert_eq!(
+                ink_voting_dapp.get_owner_of_election(1),
+                ink_env::test::default_accounts::<ink_env::DefaultEnvironment>().alice
+            );
+        }
+        #[ink::test]
+        fn count_open_elections_works() {
+            let mut ink_voting_dapp = initialize_and_create_election(false).unwrap();
+            assert_eq!(ink_voting_dapp.count_open_elections(), 0);
+            ink_voting_dapp
+                .create_election(to_ut8("secondelection"), false, vec![to_ut8("proposal")])
+                .unwrap();
+            ink_voting_dapp
+                .create_election(to_ut8("thirdelection"), false, vec![to_ut8("proposal")])
+                .unwrap();
+            ink_voting_dapp.open_election(1).unwrap();
+            ink_voting_dapp.open_election(3).unwrap();
+            assert_eq!(ink_voting_dapp.count_open_elections(), 2);
+            ink_voting_dapp.close_election(1).unwrap();
+            assert_eq!(ink_voting_dapp.count_open_elections(), 1);
+        }
+        #[ink::test]
+        fn get_all_elections_paged_works() {
+            let mut ink_voting_dapp = initialize_and_create_election(false).unwrap();
+            ink_voting_dapp
+                .create_election(to_ut8("secondelection"), true, vec![to_ut8("proposal")])
+                .unwrap();
+            ink_voting_dapp
+                .create_election(to_ut8("thirdelection"), false, vec![to_ut8("proposal")])
+                .unwrap();
+            ink_voting_dapp.open_election(1).unwrap();
+            ink_voting_dapp.open_registration(2).unwrap();
+            assert_eq!(
+                ink_voting_dapp.get_all_elections_paged(0, 2),
+                vec![
+                    (1, to_ut8("firstelection"), false, true),
+                    (2, to_ut8("secondelection"), true, false),
+                ]
+            );
+            assert_eq!(
+                ink_voting_dapp.get_all_elections_paged(2, 2),
+                vec![(3, to_ut8("thirdelection"), false, false)]
+            );
+            assert_eq!(ink_voting_dapp.get_all_elections_paged(3, 2), vec![]);
+        }
+        #[ink::test]
+        fn get_all_elections_paged_is_bounded() {
+            let mut ink_voting_dapp = InkVotingDapp::new();
+            for i in 0..30u8 {
+                ink_voting_dapp
+                    .create_election(vec![i], false, vec![to_ut8("proposal")])
+                    .unwrap();
+            }
+            assert_eq!(
+                ink_voting_dapp.get_all_elections_paged(0, 100).len(),
+                MAX_PAGE_SIZE as usize
+            );
+        }
+        #[ink::test]
+        fn register_batch_works() {
+            let accounts = ink_env::test::default_accounts::<ink_env::DefaultEnvironment>();
+            let mut ink_voting_dapp = initialize_and_create_election(true).unwrap();
+            let voters = vec![
+                accounts.bob,
+                accounts.charlie,
+                accounts.bob,
+                accounts.django,
+            ];
+            assert_eq!(
+                ink_voting_dapp.register_batch(1, voters.clone()),
+                Err(Error::RegistrationClosed)
+            );
+            ink_voting_dapp.open_registration(1).unwrap();
+            ink_voting_dapp.register(1, accounts.charlie).unwrap();
+            assert_eq!(ink_voting_dapp.register_batch(1, voters), Ok(()));
+            assert!(ink_voting_dapp.is_account_registered(1, accounts.bob));
+            assert!(ink_voting_dapp.is_account_registered(1, accounts.django));
+            assert_eq!(ink_voting_dapp.get_voter_count(1), 3);
+            let events = ink_env::test::recorded_events().collect::<Vec<_>>();
+            assert_eq!(events.len(), 4);
+            assert!(matches!(
+                decode_event(&events[3]),
+                Event::BatchRegistered(BatchRegistered {
+                    election_id: 1,
+                    count: 2
+                })
+            ));
+        }
+        #[ink::test]
+        fn get_remaining_slots_works() {
+            let accounts = ink_env::test::default_accounts::<ink_env::DefaultEnvironment>();
+            let mut ink_voting_dapp = InkVotingDapp::new();
+            ink_voting_dapp
+                .create_election_with_config(
+                    to_ut8("capped"),
+                    true,
+                    vec![to_ut8("firstproposal")],
+                    ElectionConfig {
+                        max_voters: 2,
+                        ..Default::default()
+                    },
+                )
+                .unwrap();
+            ink_voting_dapp
+                .create_election(to_ut8("uncapped"), true, vec![to_ut8("firstproposal")])
+                .unwrap();
+            assert_eq!(ink_voting_dapp.get_remaining_slots(1), 2);
+            assert_eq!(ink_voting_dapp.get_remaining_slots(2), u32::MAX);
+            ink_voting_dapp.open_registration(1).unwrap();
+            ink_voting_dapp.register(1, accounts.bob).unwrap();
+            assert_eq!(ink_voting_dapp.get_remaining_slots(1), 1);
+            assert_eq!(
+                ink_voting_dapp.seed_weights(1, vec![(accounts.charlie, 1), (accounts.django, 1)]),
+                Err(Error::VoterLimitReached)
+            );
+            assert_eq!(ink_voting_dapp.get_remaining_slots(1), 1);
+            assert_eq!(
+                ink_voting_dapp.seed_weights(1, vec![(accounts.charlie, 1), (accounts.charlie, 2)]),
+                Ok(())
+            );
+            assert_eq!(ink_voting_dapp.get_voter_weigth(1, accounts.charlie), 2);
+            assert_eq!(ink_voting_dapp.get_remaining_slots(1), 0);
+            assert_eq!(
+                ink_voting_dapp.register(1, accounts.django),
+                Err(Error::VoterLimitReached)
+            );
+            assert_eq!(ink_voting_dapp.get_remaining_slots(1), 0);
+        }
+        #[ink::test]
+        fn export_election_works() {
+            let accounts = ink_env::test::default_accounts::<ink_env::DefaultEnvironment>();
+            let mut ink_voting_dapp = initialize_and_create_election(true).unwrap();
+            assert_eq!(
+                ink_voting_dapp.export_election(2),
+                Err(Error::ElectionNotValid)
+            );
+            ink_voting_dapp.open_registration(1).unwrap();
+            ink_voting_dapp.register_me(1).unwrap();
+            ink_voting_dapp.open_election(1).unwrap();
+            ink_voting_dapp
+                .vote(1, to_ut8("secondproposal"), 1)
+                .unwrap();
+            assert_eq!(
+                ink_voting_dapp.export_election(1),
+                Ok((
+                    to_ut8("firstelection"),
+                    accounts.alice,
+                    true,
+                    vec![(to_ut8("firstproposal"), 0), (to_ut8("secondproposal"), 1)]
+                ))
+            );
+        }
+        #[ink::test]
+        fn export_roll_works() {
+            let accounts = ink_env::test::default_accounts::<ink_env::DefaultEnvironment>();
+            let mut ink_voting_dapp = InkVotingDapp::new();
+            create_three_proposal_election(&mut ink_voting_dapp);
+            assert!(ink_voting_dapp.export_roll(1, 0, 10).is_empty());
+            ink_voting_dapp
+                .seed_weights(
+                    1,
+                    vec![
+                        (accounts.alice, 5),
+                        (accounts.bob, 3),
+                        (accounts.charlie, 2),
+                    ],
+                )
+                .unwrap();
+            ink_voting_dapp.open_election(1).unwrap();
+            ink_voting_dapp.vote(1, to_ut8("firstproposal"), 2).unwrap();
+            ink_env::test::set_caller::<ink_env::DefaultEnvironment>(accounts.charlie);
+            ink_voting_dapp
+                .vote(1, to_ut8("secondproposal"), 2)
+                .unwrap();
+            assert_eq!(
+                ink_voting_dapp.export_roll(1, 0, 10),
+                vec![
+                    (accounts.alice, 3, false),
+                    (accounts.bob, 3, false),
+                    (accounts.charlie, 0, true)
+                ]
+            );
+            assert_eq!(
+                ink_voting_dapp.export_roll(1, 1, 1),
+                vec![(accounts.bob, 3, false)]
+            );
+            assert!(ink_voting_dapp.export_roll(1, 3, 10).is_empty());
+        }
+        #[ink::test]
+        fn dump_results_works() {
+            let accounts = ink_env::test::default_accounts::<ink_env::DefaultEnvironment>();
+            let mut ink_voting_dapp = InkVotingDapp::new();
+            create_three_proposal_election(&mut ink_voting_dapp);
+            assert_eq!(
+                ink_voting_dapp.dump_results(2, 0, 2),
+                Err(Error::ElectionNotValid)
+            );
+            ink_voting_dapp.open_election(1).unwrap();
+            vote_as(&mut ink_voting_dapp, accounts.bob, 1, "thirdproposal");
+            assert_eq!(
+                ink_voting_dapp.dump_results(1, 0, 2),
+                Ok(vec![
+                    (to_ut8("firstproposal"), 0),
+                    (to_ut8("secondproposal"), 0)
+                ])
+            );
+            assert_eq!(
+                ink_voting_dapp.dump_results(1, 2, 2),
+                Ok(vec![(to_ut8("thirdproposal"), 1)])
+            );
+            let emitted_events = ink_env::test::recorded_events().collect::<Vec<_>>();
+            let chunks: Vec<_> = emitted_events
+                .iter()
+                .filter_map(|event| match decode_event(event) {
+                    Event::ResultChunk(chunk) => Some((chunk.start, chunk.results)),
+                    _ => None,
+                })
+                .collect();
+            assert_eq!(
+                chunks,
+                vec![
+                    (
+                        0,
+                        vec![(to_ut8("firstproposal"), 0), (to_ut8("secondproposal"), 0)]
+                    ),
+                    (2, vec![(to_ut8("thirdproposal"), 1)])
+                ]
+            );
+        }
+        #[ink::test]
+        fn hidden_results_work() {
+            let accounts = ink_env::test::default_accounts::<ink_env::DefaultEnvironment>();
+            let mut ink_voting_dapp = InkVotingDapp::new();
+            ink_voting_dapp
+                .create_election_with_config(
+                    to_ut8("hidden"),
+                    false,
+                    vec![to_ut8("firstproposal"), to_ut8("secondproposal")],
+                    ElectionConfig {
+                        hide_results: true,
+                        ..Default::default()
+                    },
+                )
+                .unwrap();
+            ink_voting_dapp.open_election(1).unwrap();
+            vote_as(&mut ink_voting_dapp, accounts.bob, 1, "secondproposal");
+            assert_eq!(
+                ink_voting_dapp.get_result_election(1),
+                Err(Error::ResultsHidden)
+            );
+            assert_eq!(
+                ink_voting_dapp.get_votes_proposal(1, to_ut8("secondproposal")),
+                Err(Error::ResultsHidden)
+            );
+            assert_eq!(ink_voting_dapp.get_winner(1), Err(Error::ResultsHidden));
+            assert_eq!(
+                ink_voting_dapp.get_distribution(1),
+                Err(Error::ResultsHidden)
+            );
+            ink_env::test::set_caller::<ink_env::DefaultEnvironment>(accounts.alice);
+            assert_eq!(
+                ink_voting_dapp.get_winner(1),
+                Ok((to_ut8("secondproposal"), 1))
+            );
+            ink_voting_dapp.close_election(1).unwrap();
+            ink_env::test::set_caller::<ink_env::DefaultEnvironment>(accounts.bob);
+            assert_eq!(
+                ink_voting_dapp.get_result_election(1),
+                Ok(vec![
+                    (to_ut8("firstproposal"), 0),
+                    (to_ut8("secondproposal"), 1)
+                ])
+            );
+            assert_eq!(
+                ink_voting_dapp.get_votes_proposal(1, to_ut8("secondproposal")),
+                Ok(1)
+            );
+            assert_eq!(
+                ink_voting_dapp.get_winner(1),
+                Ok((to_ut8("secondproposal"), 1))
+            );
+        }
+        #[ink::test]
+        fn hidden_results_gate_every_tally() {
+            let accounts = ink_env::test::default_accounts::<ink_env::DefaultEnvironment>();
+            let mut ink_voting_dapp = InkVotingDapp::new();
+            ink_voting_dapp
+                .create_election_with_config(
+                    to_ut8("hidden"),
+                    false,
+                    vec![to_ut8("firstproposal"), to_ut8("secondproposal")],
+                    ElectionConfig {
+                        hide_results: true,
+                        ..Default::default()
+                    },
+                )
+                .unwrap();
+            ink_voting_dapp.open_election(1).unwrap();
+            vote_as(&mut ink_voting_dapp, accounts.bob, 1, "secondproposal");
+            let emitted_events = ink_env::test::recorded_events().collect::<Vec<_>>();
+            match decode_event(emitted_events.last().unwrap()) {
+                Event::Voted(Voted {
+                    proposal,
+                    weight,
+                    new_total,
+                    ..
+                }) => assert_eq!((proposal, weight, new_total), (Vec::new(), 0, 0)),
+                _ => panic!("expected Voted"),
+            }
+            assert_eq!(
+                ink_voting_dapp.get_top_proposals(1, 1),
+                Err(Error::ResultsHidden)
+            );
+            assert_eq!(
+                ink_voting_dapp.get_proposal_rank(1, to_ut8("secondproposal")),
+                Err(Error::ResultsHidden)
+            );
+            assert_eq!(ink_voting_dapp.get_runner_up(1), Err(Error::ResultsHidden));
+            assert_eq!(
+                ink_voting_dapp.get_winning_margin(1),
+                Err(Error::ResultsHidden)
+            );
+            assert_eq!(
+                ink_voting_dapp.get_results_by_id(1),
+                Err(Error::ResultsHidden)
+            );
+            assert_eq!(
+                ink_voting_dapp.export_election(1),
+                Err(Error::ResultsHidden)
+            );
+            assert_eq!(
+                ink_voting_dapp.get_results_vs_supply(1),
+                Err(Error::ResultsHidden)
+            );
+            assert_eq!(
+                ink_voting_dapp.get_final_outcome(1),
+                Err(Error::ResultsHidden)
+            );
+            assert_eq!(
+                ink_voting_dapp.get_motion_result(1),
+                Err(Error::ResultsHidden)
+            );
+            assert!(ink_voting_dapp
+                .get_results_for_elections(vec![1])
+                .is_empty());
+            ink_env::test::set_caller::<ink_env::DefaultEnvironment>(accounts.alice);
+            ink_voting_dapp.close_election(1).unwrap();
+            ink_env::test::set_caller::<ink_env::DefaultEnvironment>(accounts.bob);
+            assert_eq!(
+                ink_voting_dapp.get_top_proposals(1, 1),
+                Ok(vec![(to_ut8("secondproposal"), 1)])
+            );
+            assert_eq!(ink_voting_dapp.get_winning_margin(1), Ok(1));
+            assert_eq!(
+                ink_voting_dapp.get_results_for_elections(vec![1]),
+                vec![(
+                    1,
+                    vec![(to_ut8("firstproposal"), 0), (to_ut8("secondproposal"), 1)]
+                )]
+            );
+        }
+        #[ink::test]
+        fn get_results_by_id_works() {
+            let accounts = ink_env::test::default_accounts::<ink_env::DefaultEnvironment>();
+            let mut ink_voting_dapp = InkVotingDapp::new();
+            create_three_proposal_election(&mut ink_voting_dapp);
+            ink_voting_dapp.open_election(1).unwrap();
+            vote_as(&mut ink_voting_dapp, accounts.alice, 1, "thirdproposal");
+            vote_as(&mut ink_voting_dapp, accounts.bob, 1, "thirdproposal");
+            vote_as(&mut ink_voting_dapp, accounts.charlie, 1, "firstproposal");
+            assert_eq!(
+                ink_voting_dapp.get_results_by_id(1).unwrap(),
+                vec![(1, 1), (2, 0), (3, 2)]
+            );
+            assert_eq!(ink_voting_dapp.get_results_by_id(2).unwrap(), Vec::new());
+        }
+        #[ink::test]
+        fn get_results_hash_works() {
+            let mut ink_voting_dapp = initialize_and_create_election(false).unwrap();
+            let expected = |results: Vec<(Vec<u8>, u128)>| {
+                let mut hash = [0u8; 32];
+                ink_env::hash_bytes::<ink_env::hash::Blake2x256>(
+                    &scale::Encode::encode(&results),
+                    &mut hash,
+                );
+                hash
+            };
+            let before = ink_voting_dapp.get_results_hash(1).unwrap();
+            assert_eq!(
+                before,
+                expected(vec![
+                    (to_ut8("firstproposal"), 0),
+                    (to_ut8("secondproposal"), 0)
+                ])
+            );
+            ink_voting_dapp.open_election(1).unwrap();
+            ink_voting_dapp
+                .vote(1, to_ut8("secondproposal"), 1)
+                .unwrap();
+            let after = ink_voting_dapp.get_results_hash(1).unwrap();
+            assert_ne!(before, after);
+            assert_eq!(
+                after,
+                expected(vec![
+                    (to_ut8("firstproposal"), 0),
+                    (to_ut8("secondproposal"), 1)
+                ])
+            );
+        }
+        #[ink::test]
+        fn get_results_hash_hidden_fails() {
+            let accounts = ink_env::test::default_accounts::<ink_env::DefaultEnvironment>();
+            let mut ink_voting_dapp = InkVotingDapp::new();
+            ink_voting_dapp
+                .create_election_with_config(
+                    to_ut8("hidden"),
+                    false,
+                    vec![to_ut8("firstproposal"), to_ut8("secondproposal")],
+                    ElectionConfig {
+                        hide_results: true,
+                        ..Default::default()
+                    },
+                )
+                .unwrap();
+            ink_voting_dapp.open_election(1).unwrap();
+            vote_as(&mut ink_voting_dapp, accounts.bob, 1, "secondproposal");
+            assert_eq!(
+                ink_voting_dapp.get_results_hash(1),
+                Err(Error::ResultsHidden)
+            );
+            ink_env::test::set_caller::<ink_env::DefaultEnvironment>(accounts.alice);
+            assert!(ink_voting_dapp.get_results_hash(1).is_ok());
+            ink_voting_dapp.close_election(1).unwrap();
+            ink_env::test::set_caller::<ink_env::DefaultEnvironment>(accounts.bob);
+            assert!(ink_voting_dapp.get_results_hash(1).is_ok());
+        }
+        #[ink::test]
+        fn get_results_for_elections_works() {
+            let mut ink_voting_dapp = initialize_and_create_election(false).unwrap();
+            create_three_proposal_election(&mut ink_voting_dapp);
+            ink_voting_dapp.open_election(2).unwrap();
+            ink_voting_dapp.vote(2, to_ut8("thirdproposal"), 1).unwrap();
+            assert_eq!(
+                ink_voting_dapp.get_results_for_elections(vec![2, 3, 1]),
+                vec![
+                    (
+                        2,
+                        vec![
+                            (to_ut8("firstproposal"), 0),
+                            (to_ut8("secondproposal"), 0),
+                            (to_ut8("thirdproposal"), 1),
+                        ]
+                    ),
+                    (
+                        1,
+                        vec![(to_ut8("firstproposal"), 0), (to_ut8("secondproposal"), 0)]
+                    ),
+                ]
+            );
+        }
+        #[ink::test]
+        fn remove_proposal_works() {
+            let mut ink_voting_dapp = InkVotingDapp::new();
+            create_three_proposal_election(&mut ink_voting_dapp);
+            assert_eq!(
+                ink_voting_dapp.remove_proposal(1, to_ut8("unknown")),
+                Err(Error::InvalidProposal)
+            );
+            assert_eq!(
+                ink_voting_dapp.remove_proposal(1, to_ut8("firstproposal")),
+                Ok(())
+            );
+            assert_eq!(
+                ink_voting_dapp.get_proposals_with_ids(1),
+                vec![(1, to_ut8("secondproposal")), (2, to_ut8("thirdproposal"))]
+            );
+            assert!(!ink_voting_dapp.proposal_exists(1, to_ut8("firstproposal")));
+            assert_eq!(
+                ink_voting_dapp.remove_proposal(1, to_ut8("thirdproposal")),
+                Ok(())
+            );
+            assert_eq!(
+                ink_voting_dapp.remove_proposal(1, to_ut8("secondproposal")),
+                Err(Error::InsufficientProposals)
+            );
+            assert!(ink_voting_dapp.has_votable_proposals(1));
+            assert!(!ink_voting_dapp.has_votable_proposals(2));
+            ink_voting_dapp.open_election(1).unwrap();
+            ink_voting_dapp
+                .vote(1, to_ut8("secondproposal"), 1)
+                .unwrap();
+            assert_eq!(
+                ink_voting_dapp.get_result_election(1).unwrap(),
+                vec![(to_ut8("secondproposal"), 1)]
+            );
+        }
+        #[ink::test]
+        fn removed_proposal_cannot_win() {
+            let accounts = ink_env::test::default_accounts::<ink_env::DefaultEnvironment>();
+            let mut ink_voting_dapp = InkVotingDapp::new();
+            create_three_proposal_election(&mut ink_voting_dapp);
+            ink_voting_dapp
+                .remove_proposal(1, to_ut8("thirdproposal"))
+                .unwrap();
+            ink_voting_dapp.open_election(1).unwrap();
+            assert_eq!(
+                ink_voting_dapp.vote(1, to_ut8("thirdproposal"), 1),
+                Err(Error::InvalidProposal)
+            );
+            vote_as(&mut ink_voting_dapp, accounts.bob, 1, "secondproposal");
+            assert_eq!(
+                ink_voting_dapp.get_winner(1).unwrap(),
+                (to_ut8("secondproposal"), 1)
+            );
+            assert_eq!(
+                ink_voting_dapp
+                    .get_proposal_rank(1, to_ut8("thirdproposal"))
+                    .unwrap(),
+                0
+            );
+            assert!(ink_voting_dapp
+                .get_distribution(1)
+                .unwrap()
+                .iter()
+                .all(|(proposal, _, _)| *proposal != to_ut8("thirdproposal")));
+            assert_eq!(
+                ink_voting_dapp.get_results_by_id(1).unwrap(),
+                vec![(1, 0), (2, 1)]
+            );
+        }
+        #[ink::test]
+        fn remove_proposal_after_lock_fails() {
+            let mut ink_voting_dapp = InkVotingDapp::new();
+            create_three_proposal_election(&mut ink_voting_dapp);
+            ink_voting_dapp.lock_proposals(1).unwrap();
+            assert_eq!(
+                ink_voting_dapp.remove_proposal(1, to_ut8("firstproposal")),
+                Err(Error::ProposalsLocked)
+            );
+        }
+        #[ink::test]
+        fn get_proposals_sorted_works() {
+            let mut ink_voting_dapp = InkVotingDapp::new();
+            ink_voting_dapp
+                .create_election(
+                    to_ut8("unsorted"),
+                    false,
+                    vec![to_ut8("charlie"), to_ut8("alice"), to_ut8("bob")],
+                )
+                .unwrap();
+            assert_eq!(
+                ink_voting_dapp.get_proposals_sorted(1),
+                vec![to_ut8("alice"), to_ut8("bob"), to_ut8("charlie")]
+            );
+            assert_eq!(
+                ink_voting_dapp.get_proposal_by_index(1, 0),
+                to_ut8("charlie")
+            );
+        }
+        #[ink::test]
+        fn update_metadata_works() {
+            let accounts = ink_env::test::default_accounts::<ink_env::DefaultEnvironment>();
+            let mut ink_voting_dapp = initialize_and_create_election(false).unwrap();
+            assert_eq!(ink_voting_dapp.get_metadata(1), Vec::<u8>::new());
+            assert_eq!(
+                ink_voting_dapp.update_metadata(2, to_ut8("description")),
+                Err(Error::ElectionNotValid)
+            );
+            ink_env::test::set_caller::<ink_env::DefaultEnvironment>(accounts.bob);
+            assert_eq!(
+                ink_voting_dapp.update_metadata(1, to_ut8("description")),
+                Err(Error::OnlyOwner)
+            );
+            ink_env::test::set_caller::<ink_env::DefaultEnvironment>(accounts.alice);
+            assert_eq!(
+                ink_voting_dapp.update_metadata(1, to_ut8("description")),
+                Ok(())
+            );
+            assert_eq!(ink_voting_dapp.get_metadata(1), to_ut8("description"));
+            ink_voting_dapp.close_and_finalize(1).unwrap();
+            assert_eq!(
+                ink_voting_dapp.update_metadata(1, to_ut8("corrected")),
+                Ok(())
+            );
+            assert_eq!(ink_voting_dapp.get_metadata(1), to_ut8("corrected"));
+            let emitted_events = ink_env::test::recorded_events().collect::<Vec<_>>();
+            match decode_event(emitted_events.last().unwrap()) {
+                Event::MetadataUpdated(MetadataUpdated {
+                    election_id,
+                    metadata,
+                }) => {
+                    assert_eq!(election_id, 1);
+                    assert_eq!(metadata, to_ut8("corrected"));
+                }
+                _ => panic!("expected MetadataUpdated"),
+            }
+        }
+        #[ink::test]
+        fn reorder_proposals_works() {
+            let accounts = ink_env::test::default_accounts::<ink_env::DefaultEnvironment>();
+            let mut ink_voting_dapp = InkVotingDapp::new();
+            create_three_proposal_election(&mut ink_voting_dapp);
+            assert_eq!(ink_voting_dapp.get_proposal_order(1), vec![1, 2, 3]);
+            for order in [vec![3, 1], vec![3, 1, 1], vec![3, 1, 4], vec![3, 1, 2, 4]] {
+                assert_eq!(
+                    ink_voting_dapp.reorder_proposals(1, order),
+                    Err(Error::InvalidOrder)
+                );
+            }
+            ink_env::test::set_caller::<ink_env::DefaultEnvironment>(accounts.bob);
+            assert_eq!(
+                ink_voting_dapp.reorder_proposals(1, vec![3, 1, 2]),
+                Err(Error::OnlyOwner)
+            );
+            ink_env::test::set_caller::<ink_env::DefaultEnvironment>(accounts.alice);
+            assert_eq!(ink_voting_dapp.reorder_proposals(1, vec![3, 1, 2]), Ok(()));
+            assert_eq!(ink_voting_dapp.get_proposal_order(1), vec![3, 1, 2]);
+            ink_voting_dapp
+                .remove_proposal(1, to_ut8("firstproposal"))
+                .unwrap();
+            assert_eq!(ink_voting_dapp.get_proposal_order(1), vec![2, 1]);
+            assert_eq!(
+                ink_voting_dapp.get_proposals_with_ids(1),
+                vec![(1, to_ut8("secondproposal")), (2, to_ut8("thirdproposal"))]
+            );
+            ink_voting_dapp.lock_proposals(1).unwrap();
+            assert_eq!(
+                ink_voting_dapp.reorder_proposals(1, vec![1, 2]),
+                Err(Error::ProposalsLocked)
+            );
+        }
+        #[ink::test]
+        fn get_proposals_with_ids_works() {
+            let mut ink_voting_dapp = InkVotingDapp::new();
+            create_three_proposal_election(&mut ink_voting_dapp);
+            assert_eq!(
+                ink_voting_dapp.get_proposals_with_ids(1),
+                vec![
+                    (1, to_ut8("firstproposal")),
+                    (2, to_ut8("secondproposal")),
+                    (3, to_ut8("thirdproposal")),
+                ]
+            );
+            assert_eq!(ink_voting_dapp.get_proposals_with_ids(2), Vec::new());
+        }
+        #[ink::test]
+        fn get_proposal_by_index_works() {
+            let ink_voting_dapp = initialize_and_create_election(false).unwrap();
+            assert_eq!(
+                ink_voting_dapp.get_proposal_by_index(1, 0),
+                to_ut8("firstproposal")
+            );
+            assert_eq!(
+                ink_voting_dapp.get_proposal_by_index(1, 1),
+                to_ut8("secondproposal")
+            );
+            assert_eq!(
+                ink_voting_dapp.get_proposal_by_index(1, 2),
+                Vec::<u8>::new()
+            );
+            assert_eq!(
+                ink_voting_dapp.get_proposal_by_index(2, 0),
+                Vec::<u8>::new()
+            );
+        }
+        #[ink::test]
+        fn get_created_at_works() {
+            ink_env::test::advance_block::<ink_env::DefaultEnvironment>();
+            let ink_voting_dapp = initialize_and_create_election(false).unwrap();
+            assert!(ink_voting_dapp.get_created_at(1) > 0);
+            assert_eq!(
+                ink_voting_dapp.get_created_at(1),
+                ink_env::block_timestamp::<ink_env::DefaultEnvironment>()
+            );
+            assert_eq!(ink_voting_dapp.get_created_at(2), 0);
+        }
+        #[ink::test]
+        fn max_vote_weight_works() {
+            let accounts = ink_env::test::default_accounts::<ink_env::DefaultEnvironment>();
+            let mut ink_voting_dapp = InkVotingDapp::new();
+            create_three_proposal_election(&mut ink_voting_dapp);
+            ink_voting_dapp
+                .seed_weights(1, vec![(accounts.alice, 10), (accounts.bob, 10)])
+                .unwrap();
+            ink_voting_dapp.open_election(1).unwrap();
+            ink_env::test::set_caller::<ink_env::DefaultEnvironment>(accounts.bob);
+            assert_eq!(
+                ink_voting_dapp.set_max_vote_weight(5),
+                Err(Error::OnlyAdmin)
+            );
+            ink_env::test::set_caller::<ink_env::DefaultEnvironment>(accounts.alice);
+            assert_eq!(ink_voting_dapp.set_max_vote_weight(5), Ok(()));
+            assert_eq!(ink_voting_dapp.get_max_vote_weight(), 5);
+            assert_eq!(
+                ink_voting_dapp.vote(1, to_ut8("firstproposal"), 6),
+                Err(Error::WeightTooLarge)
+            );
+            assert_eq!(
+                ink_voting_dapp.vote_split(
+                    1,
+                    vec![(to_ut8("firstproposal"), 3), (to_ut8("secondproposal"), 3)]
+                ),
+                Err(Error::WeightTooLarge)
+            );
+            assert_eq!(ink_voting_dapp.get_voter_weigth(1, accounts.alice), 10);
+            assert_eq!(
+                ink_voting_dapp.vote_split(
+                    1,
+                    vec![(to_ut8("firstproposal"), 3), (to_ut8("secondproposal"), 2)]
+                ),
+                Ok(())
+            );
+            ink_voting_dapp.set_max_vote_weight(0).unwrap();
+            ink_env::test::set_caller::<ink_env::DefaultEnvironment>(accounts.bob);
+            assert_eq!(ink_voting_dapp.vote(1, to_ut8("firstproposal"), 10), Ok(()));
+        }
+        #[ink::test]
+        fn max_vote_weight_allows_exact_cap() {
+            let accounts = ink_env::test::default_accounts::<ink_env::DefaultEnvironment>();
+            let mut ink_voting_dapp = InkVotingDapp::new();
+            create_three_proposal_election(&mut ink_voting_dapp);
+            ink_voting_dapp
+                .seed_weights(1, vec![(accounts.alice, 10)])
+                .unwrap();
+            ink_voting_dapp.open_election(1).unwrap();
+            ink_voting_dapp.set_max_vote_weight(5).unwrap();
+            assert_eq!(ink_voting_dapp.vote(1, to_ut8("firstproposal"), 5), Ok(()));
+            assert_eq!(
+                ink_voting_dapp
+                    .get_votes_proposal(1, to_ut8("firstproposal"))
+                    .unwrap(),
+                5
+            );
+        }
+        #[ink::test]
+        fn creation_limit_works() {
+            let accounts = ink_env::test::default_accounts::<ink_env::DefaultEnvironment>();
+            let mut ink_voting_dapp = InkVotingDapp::new();
+            ink_env::test::set_caller::<ink_env::DefaultEnvironment>(accounts.bob);
+            assert_eq!(
+                ink_voting_dapp.set_max_elections_per_account(2),
+                Err(Error::OnlyAdmin)
+            );
+            ink_env::test::set_caller::<ink_env::DefaultEnvironment>(accounts.alice);
+            assert_eq!(ink_voting_dapp.set_max_elections_per_account(2), Ok(()));
+            ink_env::test::set_caller::<ink_env::DefaultEnvironment>(accounts.bob);
+            let proposals = vec![to_ut8("firstproposal")];
+            for name in ["first", "second"] {
+                assert_eq!(
+                    ink_voting_dapp.create_election(to_ut8(name), false, proposals.clone()),
+                    Ok(())
+                );
+            }
+            assert_eq!(
+                ink_voting_dapp.create_election(to_ut8("third"), false, proposals.clone()),
+                Err(Error::CreationLimitReached)
+            );
+            ink_env::test::set_caller::<ink_env::DefaultEnvironment>(accounts.alice);
+            assert_eq!(
+                ink_voting_dapp.create_election(to_ut8("third"), false, proposals.clone()),
+                Ok(())
+            );
+            ink_voting_dapp.set_max_elections_per_account(0).unwrap();
+            ink_env::test::set_caller::<ink_env::DefaultEnvironment>(accounts.bob);
+            assert_eq!(
+                ink_voting_dapp.create_election(to_ut8("fourth"), false, proposals),
+                Ok(())
+            );
+        }
+        #[ink::test]
+        fn paused_contract_rejects_creation() {
+            let mut ink_voting_dapp = initialize_and_create_election(false).unwrap();
+            let bob = ink_env::test::default_accounts::<ink_env::DefaultEnvironment>().bob;
+            ink_env::test::set_caller::<ink_env::DefaultEnvironment>(bob);
+            assert_eq!(ink_voting_dapp.set_paused(true), Err(Error::OnlyAdmin));
+            let alice = ink_env::test::default_accounts::<ink_env::DefaultEnvironment>().alice;
+            ink_env::test::set_caller::<ink_env::DefaultEnvironment>(alice);
+            assert_eq!(ink_voting_dapp.set_paused(true), Ok(()));
+            assert!(ink_voting_dapp.is_paused());
+            assert_eq!(
+                ink_voting_dapp.create_election(
+                    to_ut8("secondelection"),
+                    false,
+                    vec![to_ut8("proposal")]
+                ),
+                Err(Error::ContractPaused)
+            );
+            assert_eq!(
+                ink_voting_dapp.get_election_list(),
+                vec![to_ut8("firstelection")]
+            );
+            assert_eq!(ink_voting_dapp.get_number_elections(), 1);
+            ink_voting_dapp.set_paused(false).unwrap();
+            ink_voting_dapp
+                .create_election(to_ut8("secondelection"), false, vec![to_ut8("proposal")])
+                .unwrap();
+            assert_eq!(ink_voting_dapp.get_election_id(to_ut8("secondelection")), 2);
+        }
+        #[ink::test]
+        fn try_get_election_id_works() {
+            let ink_voting_dapp = initialize_and_create_election(false).unwrap();
+            assert_eq!(
+                ink_voting_dapp.try_get_election_id(to_ut8("firstelection")),
+                Ok(1)
+            );
+            assert_eq!(
+                ink_voting_dapp.try_get_election_id(to_ut8("unknown")),
+                Err(Error::ElectionNotValid)
+            );
+            assert_eq!(ink_voting_dapp.get_election_id(to_ut8("unknown")), 0);
+        }
+        #[ink::test]
+        fn election_id_exists_works() {
+            let ink_voting_dapp = initialize_and_create_election(false).unwrap();
+            assert!(ink_voting_dapp.election_id_exists(1));
+            assert!(!ink_voting_dapp.election_id_exists(0));
+            assert!(!ink_voting_dapp.election_id_exists(2));
+        }
+        #[ink::test]
+        fn get_owner_election_count_works() {
+            let accounts = ink_env::test::default_accounts::<ink_env::DefaultEnvironment>();
+            let mut ink_voting_dapp = initialize_and_create_election(false).unwrap();
+            create_three_proposal_election(&mut ink_voting_dapp);
+            assert_eq!(ink_voting_dapp.get_owner_election_count(accounts.alice), 2);
+            assert_eq!(ink_voting_dapp.get_owner_election_count(accounts.bob), 0);
+            ink_voting_dapp.change_ownership(1, accounts.bob).unwrap();
+            assert_eq!(ink_voting_dapp.get_owner_election_count(accounts.alice), 1);
+            assert_eq!(ink_voting_dapp.get_owner_election_count(accounts.bob), 1);
+        }
+        #[ink::test]
+        fn storage_version_works() {
+            let mut ink_voting_dapp = initialize_and_create_election(false).unwrap();
+            assert_eq!(ink_voting_dapp.get_storage_version(), STORAGE_VERSION);
+            ink_voting_dapp.open_election(1).unwrap();
+            ink_voting_dapp.storage_version = STORAGE_VERSION + 1;
+            assert_eq!(
+                ink_voting_dapp.vote(1, to_ut8("firstproposal"), 1),
+                Err(Error::VersionMismatch)
+            );
+            assert_eq!(
+                ink_voting_dapp.create_election(to_ut8("second"), false, vec![to_ut8("a")]),
+                Err(Error::VersionMismatch)
+            );
+            assert_eq!(
+                ink_voting_dapp.save_template(to_ut8("template"), vec![to_ut8("a")]),
+                Err(Error::VersionMismatch)
+            );
+            assert_eq!(
+                ink_voting_dapp.publish_as_template(1, to_ut8("template")),
+                Err(Error::VersionMismatch)
+            );
+            assert!(ink_voting_dapp
+                .get_public_template(to_ut8("template"))
+                .is_empty());
+        }
+        #[ink::test]
+        fn now_works() {
+            let ink_voting_dapp = InkVotingDapp::new();
+            let start = ink_voting_dapp.now();
+            // Each block advances the timestamp by 6 ms.
+            ink_env::test::advance_block::<ink_env::DefaultEnvironment>();
+            assert_eq!(ink_voting_dapp.now(), start + 6);
+            assert_eq!(
+                ink_voting_dapp.now(),
+                ink_env::block_timestamp::<ink_env::DefaultEnvironment>()
+            );
+        }
+        #[ink::test]
+        fn get_version_works() {
+            let ink_voting_dapp = InkVotingDapp::new();
+            assert_eq!(ink_voting_dapp.get_version(), 1);
+        }
+        #[ink::test]
+        fn proposal_exists_works() {
+            let ink_voting_dapp = initialize_and_create_election(false).unwrap();
+            assert!(ink_voting_dapp.proposal_exists(1, to_ut8("firstproposal")));
+            assert!(!ink_voting_dapp.proposal_exists(1, to_ut8("thirdproposal")));
+            assert!(!ink_voting_dapp.proposal_exists(2, to_ut8("firstproposal")));
+        }
+        #[ink::test]
+        fn is_name_available_works() {
+            let ink_voting_dapp = initialize_and_create_election(false).unwrap();
+            assert!(!ink_voting_dapp.is_name_available(to_ut8("firstelection")));
+            assert!(ink_voting_dapp.is_name_available(to_ut8("secondelection")));
+        }
+        #[ink::test]
+        fn normalized_names_collide() {
+            let mut ink_voting_dapp = InkVotingDapp::new();
+            let proposals = vec![to_ut8("firstproposal"), to_ut8("secondproposal")];
+            let config = ElectionConfig {
+                normalize_names: true,
+                ..Default::default()
+            };
+            assert_eq!(
+                ink_voting_dapp.create_election_with_config(
+                    to_ut8("  Election "),
+                    false,
+                    proposals.clone(),
+                    config.clone()
+                ),
+                Ok(())
+            );
+            assert_eq!(ink_voting_dapp.get_election_id(to_ut8("election")), 1);
+            assert_eq!(
+                ink_voting_dapp.create_election_with_config(
+                    to_ut8("ELECTION"),
+                    false,
+                    proposals.clone(),
+                    config.clone()
+                ),
+                Err(Error::ElectionNotValid)
+            );
+            assert_eq!(
+                ink_voting_dapp.create_election_with_config(
+                    to_ut8("election  "),
+                    false,
+                    proposals.clone(),
+                    config
+                ),
+                Err(Error::ElectionNotValid)
+            );
+            assert_eq!(
+                ink_voting_dapp.create_election(to_ut8("Election"), false, proposals),
+                Ok(())
+            );
+        }
+        #[ink::test]
+        fn templates_work() {
+            let accounts = ink_env::test::default_accounts::<ink_env::DefaultEnvironment>();
+            let mut ink_voting_dapp = InkVotingDapp::new();
+            let proposals = vec![to_ut8("firstproposal"), to_ut8("secondproposal")];
+            assert_eq!(
+                ink_voting_dapp.save_template(to_ut8("template"), vec![]),
+                Err(Error::InsufficientProposals)
+            );
+            assert_eq!(
+                ink_voting_dapp.save_template(to_ut8("template"), proposals.clone()),
+                Ok(())
+            );
+            assert_eq!(
+                ink_voting_dapp.get_template(accounts.alice, to_ut8("template")),
+                proposals
+            );
+            assert_eq!(
+                ink_voting_dapp.create_from_template(
+                    to_ut8("firstelection"),
+                    to_ut8("unknown"),
+                    false
+                ),
+                Err(Error::TemplateNotFound)
+            );
+            assert_eq!(
+                ink_voting_dapp.create_from_template(
+                    to_ut8("firstelection"),
+                    to_ut8("template"),
+                    true
+                ),
+                Ok(())
+            );
+            assert_eq!(
+                ink_voting_dapp.get_proposal_for_election(to_ut8("firstelection")),
+                proposals
+            );
+            assert_eq!(ink_voting_dapp.get_owner_of_election(1), accounts.alice);
+            ink_env::test::set_caller::<ink_env::DefaultEnvironment>(accounts.bob);
+            assert_eq!(
+                ink_voting_dapp.create_from_template(
+                    to_ut8("secondelection"),
+                    to_ut8("template"),
+                    false
+                ),
+                Err(Error::TemplateNotFound)
+            );
+        }
+        #[ink::test]
+        fn public_templates_work() {
+            let accounts = ink_env::test::default_accounts::<ink_env::DefaultEnvironment>();
+            let mut ink_voting_dapp = InkVotingDapp::new();
+            create_three_proposal_election(&mut ink_voting_dapp);
+            let proposals = ink_voting_dapp.get_proposal_for_election(to_ut8("threeproposals"));
+            ink_env::test::set_caller::<ink_env::DefaultEnvironment>(accounts.bob);
+            assert_eq!(
+                ink_voting_dapp.publish_as_template(1, to_ut8("public")),
+                Err(Error::OnlyOwner)
+            );
+            ink_env::test::set_caller::<ink_env::DefaultEnvironment>(accounts.alice);
+            assert_eq!(
+                ink_voting_dapp.publish_as_template(1, to_ut8("public")),
+                Ok(())
+            );
+            assert_eq!(
+                ink_voting_dapp.publish_as_template(1, to_ut8("public")),
+                Err(Error::TemplateExists)
+            );
+            let emitted_events = ink_env::test::recorded_events().collect::<Vec<_>>();
+            match decode_event(emitted_events.last().unwrap()) {
+                Event::PublishedTemplate(PublishedTemplate {
+                    election_id,
+                    name,
+                    publisher,
+                }) => {
+                    assert_eq!(election_id, 1);
+                    assert_eq!(name, to_ut8("public"));
+                    assert_eq!(publisher, accounts.alice);
+                }
+                _ => panic!("expected PublishedTemplate"),
+            }
+            assert_eq!(
+                ink_voting_dapp.get_public_template(to_ut8("public")),
+                proposals
+            );
+            assert!(ink_voting_dapp
+                .get_template(accounts.alice, to_ut8("public"))
+                .is_empty());
+            ink_env::test::set_caller::<ink_env::DefaultEnvironment>(accounts.bob);
+            assert_eq!(
+                ink_voting_dapp.create_from_template(to_ut8("cloned"), to_ut8("public"), false),
+                Ok(())
+            );
+            assert_eq!(
+                ink_voting_dapp.get_proposal_for_election(to_ut8("cloned")),
+                proposals
+            );
+            assert_eq!(ink_voting_dapp.get_owner_of_election(2), accounts.bob);
+        }
+        #[ink::test]
+        fn open_election_works() {
+            let mut ink_voting_dapp = initialize_and_create_election(false).unwrap();
+            assert_eq!(ink_voting_dapp.is_election_open(1), false);
+            assert_eq!(ink_voting_dapp.open_election(1), Ok(()));
+            assert_eq!(ink_voting_dapp.is_election_open(1), true);
+            assert_eq!(
+                ink_voting_dapp.open_election(2),
+                Err(Error::ElectionNotValid)
+            );
+            let bob = ink_env::test::default_accounts::<ink_env::DefaultEnvironment>().bob;
+            ink_env::test::set_caller::<ink_env::DefaultEnvironment>(bob);
+            assert_eq!(ink_voting_dapp.open_election(1), Err(Error::OnlyOwner));
+            assert_eq!(ink_env::test::recorded_events().count(), 2);
+        }
+        #[ink::test]
+        fn close_election_works() {
+            let mut ink_voting_dapp = initialize_and_create_election(false).unwrap();
+            ink_voting_dapp.open_election(1).unwrap();
+            assert_eq!(ink_voting_dapp.is_election_open(1), true);
+            assert_eq!(ink_voting_dapp.close_election(1), Ok(()));
+            assert_eq!(ink_voting_dapp.is_election_open(1), false);
+            assert_eq!(
+                ink_voting_dapp.open_election(2),
+                Err(Error::ElectionNotValid)
+            );
+            let bob = ink_env::test::default_accounts::<ink_env::DefaultEnvironment>().bob;
+            ink_env::test::set_caller::<ink_env::DefaultEnvironment>(bob);
+            assert_eq!(ink_voting_dapp.close_election(1), Err(Error::OnlyOwner));
+            assert_eq!(ink_env::test::recorded_events().count(), 3);
+        }
+        #[ink::test]
+        fn finalize_election_works() {
+            let mut ink_voting_dapp = initialize_and_create_election(false).unwrap();
+            assert!(!ink_voting_dapp.is_finalized(1));
+            assert_eq!(
+                ink_voting_dapp.finalize_election(2),
+                Err(Error::ElectionNotValid)
+            );
+            ink_voting_dapp.open_election(1).unwrap();
+            ink_voting_dapp
+                .vote(1, to_ut8("secondproposal"), 1)
+                .unwrap();
+            assert_eq!(
+                ink_voting_dapp.finalize_election(1),
+                Err(Error::ElectionStillOpen)
+            );
+            ink_voting_dapp.close_election(1).unwrap();
+            let bob = ink_env::test::default_accounts::<ink_env::DefaultEnvironment>().bob;
+            ink_env::test::set_caller::<ink_env::DefaultEnvironment>(bob);
+            assert_eq!(ink_voting_dapp.finalize_election(1), Err(Error::OnlyOwner));
+            let alice = ink_env::test::default_accounts::<ink_env::DefaultEnvironment>().alice;
+            ink_env::test::set_caller::<ink_env::DefaultEnvironment>(alice);
+            assert_eq!(ink_voting_dapp.finalize_election(1), Ok(()));
+            assert!(ink_voting_dapp.is_finalized(1));
+            assert_eq!(
+                ink_voting_dapp.get_finalized_result(1),
+                (to_ut8("secondproposal"), 1)
+            );
+            assert_eq!(ink_env::test::recorded_events().count(), 6);
+            assert_eq!(
+                ink_voting_dapp.finalize_election(1),
+                Err(Error::ElectionFinalized)
+            );
+        }
+        #[ink::test]
+        fn get_phase_works() {
+            let mut ink_voting_dapp = initialize_and_create_election(true).unwrap();
+            assert_eq!(ink_voting_dapp.get_phase(1), Phase::Created);
+            ink_voting_dapp.open_registration(1).unwrap();
+            assert_eq!(ink_voting_dapp.get_phase(1), Phase::RegistrationOpen);
+            ink_voting_dapp.close_registration(1).unwrap();
+            assert_eq!(ink_voting_dapp.get_phase(1), Phase::Created);
+            ink_voting_dapp.open_election(1).unwrap();
+            assert_eq!(ink_voting_dapp.get_phase(1), Phase::Voting);
+            ink_voting_dapp.close_election(1).unwrap();
+            assert_eq!(ink_voting_dapp.get_phase(1), Phase::Closed);
+            ink_voting_dapp.finalize_election(1).unwrap();
+            assert_eq!(ink_voting_dapp.get_phase(1), Phase::Finalized);
+        }
+        #[ink::test]
+        fn finalize_event_matches_storage() {
+            let mut ink_voting_dapp = initialize_and_create_election(false).unwrap();
+            ink_voting_dapp.open_election(1).unwrap();
+            ink_voting_dapp.vote(1, to_ut8("firstproposal"), 1).unwrap();
+            ink_voting_dapp.close_election(1).unwrap();
+            ink_voting_dapp.finalize_election(1).unwrap();
+            let events = ink_env::test::recorded_events().collect::<Vec<_>>();
+            let (winner, votes) = match decode_event(events.last().unwrap()) {
+                Event::FinalizeElection(FinalizeElection {
+                    election_id: 1,
+                    winner,
+                    votes,
+                }) => (winner, votes),
+                _ => panic!("expected FinalizeElection as the last event"),
+            };
+            assert_eq!(ink_voting_dapp.get_finalized_result(1), (winner, votes));
+            assert_eq!(
+                ink_voting_dapp.get_election_state(1),
+                ElectionState::ElectionClosed
+            );
+            assert_eq!(
+                ink_voting_dapp.get_election_outcome(1),
+                Some(ElectionOutcome::Passed)
+            );
+        }
+        #[ink::test]
+        fn deadline_and_window_getters_work() {
+            let accounts = ink_env::test::default_accounts::<ink_env::DefaultEnvironment>();
+            let mut ink_voting_dapp = initialize_and_create_election(true).unwrap();
+            let start = ink_env::block_timestamp::<ink_env::DefaultEnvironment>();
+            ink_voting_dapp
+                .create_election_with_config(
+                    to_ut8("windowed"),
+                    true,
+                    vec![to_ut8("firstproposal")],
+                    ElectionConfig {
+                        registration_deadline: start + 6,
+                        voting_period: 60,
+                        ..Default::default()
+                    },
+                )
+                .unwrap();
+            assert_eq!(ink_voting_dapp.get_registration_deadline(1), 0);
+            assert_eq!(ink_voting_dapp.get_registration_deadline(2), start + 6);
+            assert_eq!(ink_voting_dapp.get_voting_window(2), (0, 0));
+            ink_voting_dapp.open_registration(2).unwrap();
+            ink_voting_dapp.register(2, accounts.bob).unwrap();
+            // Each block advances the timestamp by 6 ms.
+            ink_env::test::advance_block::<ink_env::DefaultEnvironment>();
+            ink_env::test::advance_block::<ink_env::DefaultEnvironment>();
+            assert_eq!(
+                ink_voting_dapp.register(2, accounts.charlie),
+                Err(Error::RegistrationClosed)
+            );
+            ink_voting_dapp.open_election(1).unwrap();
+            ink_voting_dapp.open_election(2).unwrap();
+            assert_eq!(ink_voting_dapp.get_voting_window(1), (start + 12, 0));
+            assert_eq!(
+                ink_voting_dapp.get_voting_window(2),
+                (start + 12, start + 72)
+            );
+        }
+        #[ink::test]
+        fn get_elections_created_between_works() {
+            let mut ink_voting_dapp = InkVotingDapp::new();
+            let start = ink_env::block_timestamp::<ink_env::DefaultEnvironment>();
+            for name in ["first", "second", "third", "fourth"] {
+                ink_voting_dapp
+                    .create_election(to_ut8(name), false, vec![to_ut8("proposal")])
+                    .unwrap();
+                // Each block advances the timestamp by 6 ms.
+                ink_env::test::advance_block::<ink_env::DefaultEnvironment>();
+            }
+            assert_eq!(ink_voting_dapp.get_created_at(3), start + 12);
+            assert_eq!(
+                ink_voting_dapp.get_elections_created_between(start + 6, start + 12, 0, 10),
+                vec![2, 3]
+            );
+            assert_eq!(
+                ink_voting_dapp.get_elections_created_between(start + 6, start + 12, 2, 10),
+                vec![3]
+            );
+            assert_eq!(
+                ink_voting_dapp.get_elections_created_between(start + 100, start + 200, 0, 10),
+                Vec::<u32>::new()
+            );
+        }
+        #[ink::test]
+        fn get_elections_in_phase_works() {
+            let mut ink_voting_dapp = InkVotingDapp::new();
+            for name in ["first", "second", "third", "fourth"] {
+                ink_voting_dapp
+                    .create_election(to_ut8(name), false, vec![to_ut8("proposal")])
+                    .unwrap();
+            }
+            ink_voting_dapp.open_election(2).unwrap();
+            ink_voting_dapp.open_election(3).unwrap();
+            ink_voting_dapp.open_registration(4).unwrap();
+            assert_eq!(
+                ink_voting_dapp.get_elections_in_phase(Phase::Voting, 0, 10),
+                vec![2, 3]
+            );
+            assert_eq!(
+                ink_voting_dapp.get_elections_in_phase(Phase::Voting, 2, 10),
+                vec![3]
+            );
+            assert_eq!(
+                ink_voting_dapp.get_elections_in_phase(Phase::Voting, 0, 2),
+                vec![2]
+            );
+            assert_eq!(
+                ink_voting_dapp.get_elections_in_phase(Phase::Created, 0, 10),
+                vec![1]
+            );
+            assert_eq!(
+                ink_voting_dapp.get_elections_in_phase(Phase::RegistrationOpen, 0, 10),
+                vec![4]
+            );
+            assert_eq!(
+                ink_voting_dapp.get_elections_in_phase(Phase::Finalized, 0, 10),
+                Vec::<u32>::new()
+            );
+        }
+        #[ink::test]
+        fn close_and_finalize_works() {
+            let mut ink_voting_dapp = initialize_and_create_election(false).unwrap();
+            ink_voting_dapp.open_election(1).unwrap();
+            ink_voting_dapp
+                .vote(1, to_ut8("secondproposal"), 1)
+                .unwrap();
+            let bob = ink_env::test::default_accounts::<ink_env::DefaultEnvironment>().bob;
+            ink_env::test::set_caller::<ink_env::DefaultEnvironment>(bob);
+            assert_eq!(ink_voting_dapp.close_and_finalize(1), Err(Error::OnlyOwner));
+            let alice = ink_env::test::default_accounts::<ink_env::DefaultEnvironment>().alice;
+            ink_env::test::set_caller::<ink_env::DefaultEnvironment>(alice);
+            assert_eq!(ink_voting_dapp.close_and_finalize(1), Ok(()));
+            assert_eq!(
+                ink_voting_dapp.get_election_state(1),
+                ElectionState::ElectionClosed
+            );
+            assert!(ink_voting_dapp.is_finalized(1));
+            assert_eq!(
+                ink_voting_dapp.get_finalized_result(1),
+                (to_ut8("secondproposal"), 1)
+            );
+            let events = ink_env::test::recorded_events().collect::<Vec<_>>();
+            assert!(matches!(
+                decode_event(&events[events.len() - 2]),
+                Event::CloseElection(CloseElection { election_id: 1, .. })
+            ));
+            assert!(matches!(
+                decode_event(&events[events.len() - 1]),
+                Event::FinalizeElection(FinalizeElection {
+                    election_id: 1,
+                    votes: 1,
+                    ..
+                })
+            ));
+            assert_eq!(
+                ink_voting_dapp.close_and_finalize(1),
+                Err(Error::ElectionFinalized)
+            );
+        }
+        fn finalize_with_votes(
+            ink_voting_dapp: &mut InkVotingDapp,
+            name: &str,
+            quorum: u128,
+            proposals: &[&str],
+        ) -> Option<ElectionOutcome> {
+            let accounts = ink_env::test::default_accounts::<ink_env::DefaultEnvironment>();
+            ink_env::test::set_caller::<ink_env::DefaultEnvironment>(accounts.alice);
+            ink_voting_dapp
+                .create_election_with_config(
+                    to_ut8(name),
+                    false,
+                    vec![to_ut8("firstproposal"), to_ut8("secondproposal")],
+                    ElectionConfig {
+                        quorum,
+                        ..Default::default()
+                    },
+                )
+                .unwrap();
+            let election_id = ink_voting_dapp.get_election_id(to_ut8(name));
+            ink_voting_dapp.open_election(election_id).unwrap();
+            let voters = [accounts.bob, accounts.charlie];
+            for (voter, proposal) in voters.iter().zip(proposals) {
+                vote_as(ink_voting_dapp, *voter, election_id, proposal);
+            }
+            ink_env::test::set_caller::<ink_env::DefaultEnvironment>(accounts.alice);
+            assert_eq!(ink_voting_dapp.get_election_outcome(election_id), None);
+            ink_voting_dapp.close_and_finalize(election_id).unwrap();
+            ink_voting_dapp.get_election_outcome(election_id)
+        }
+        #[ink::test]
+        fn election_outcome_works() {
+            let mut ink_voting_dapp = InkVotingDapp::new();
+            let dapp = &mut ink_voting_dapp;
+            assert_eq!(
+                finalize_with_votes(dapp, "passed", 2, &["firstproposal", "firstproposal"]),
+                Some(ElectionOutcome::Passed)
+            );
+            assert_eq!(
+                finalize_with_votes(dapp, "failed", 3, &["firstproposal", "firstproposal"]),
+                Some(ElectionOutcome::FailedQuorum)
+            );
+            assert_eq!(
+                finalize_with_votes(dapp, "tied", 0, &["firstproposal", "secondproposal"]),
+                Some(ElectionOutcome::Tied)
+            );
+            assert_eq!(
+                finalize_with_votes(dapp, "novotes", 0, &[]),
+                Some(ElectionOutcome::NoVotes)
+            );
+        }
+        #[ink::test]
+        fn get_final_outcome_works() {
+            let accounts = ink_env::test::default_accounts::<ink_env::DefaultEnvironment>();
+            let mut ink_voting_dapp = InkVotingDapp::new();
+            ink_voting_dapp
+                .create_election_with_config(
+                    to_ut8("quorum"),
+                    false,
+                    vec![to_ut8("firstproposal"), to_ut8("secondproposal")],
+                    ElectionConfig {
+                        quorum: 2,
+                        ..Default::default()
+                    },
+                )
+                .unwrap();
+            ink_voting_dapp.open_election(1).unwrap();
+            vote_as(&mut ink_voting_dapp, accounts.alice, 1, "secondproposal");
+            assert_eq!(
+                ink_voting_dapp.get_final_outcome(1).unwrap(),
+                (to_ut8("secondproposal"), 1, false)
+            );
+            vote_as(&mut ink_voting_dapp, accounts.bob, 1, "secondproposal");
+            assert_eq!(
+                ink_voting_dapp.get_final_outcome(1).unwrap(),
+                (to_ut8("secondproposal"), 2, true)
+            );
+        }
+        #[ink::test]
+        fn voter_quorum_works() {
+            let accounts = ink_env::test::default_accounts::<ink_env::DefaultEnvironment>();
+            let mut ink_voting_dapp = InkVotingDapp::new();
+            for name in ["headcount", "below"] {
+                ink_voting_dapp
+                    .create_election_with_config(
+                        to_ut8(name),
+                        false,
+                        vec![to_ut8("firstproposal"), to_ut8("secondproposal")],
+                        ElectionConfig {
+                            min_voters: 2,
+                            ..Default::default()
+                        },
+                    )
+                    .unwrap();
+            }
+            ink_voting_dapp.open_election(1).unwrap();
+            ink_voting_dapp.open_election(2).unwrap();
+            assert!(!ink_voting_dapp.is_voter_quorum_reached(1));
+            vote_as(&mut ink_voting_dapp, accounts.bob, 1, "firstproposal");
+            vote_as(&mut ink_voting_dapp, accounts.bob, 2, "firstproposal");
+            assert!(!ink_voting_dapp.is_voter_quorum_reached(1));
+            assert_eq!(
+                ink_voting_dapp.get_final_outcome(1).unwrap(),
+                (to_ut8("firstproposal"), 1, false)
+            );
+            vote_as(&mut ink_voting_dapp, accounts.charlie, 1, "firstproposal");
+            assert!(ink_voting_dapp.is_voter_quorum_reached(1));
+            assert_eq!(
+                ink_voting_dapp.get_final_outcome(1).unwrap(),
+                (to_ut8("firstproposal"), 2, true)
+            );
+            ink_env::test::set_caller::<ink_env::DefaultEnvironment>(accounts.alice);
+            ink_voting_dapp.close_and_finalize(1).unwrap();
+            ink_voting_dapp.close_and_finalize(2).unwrap();
+            assert_eq!(
+                ink_voting_dapp.get_election_outcome(1),
+                Some(ElectionOutcome::Passed)
+            );
+            assert_eq!(
+                ink_voting_dapp.get_election_outcome(2),
+                Some(ElectionOutcome::FailedQuorum)
+            );
+        }
+        #[ink::test]
+        fn get_final_outcome_without_quorum_works() {
+            let ink_voting_dapp = initialize_and_create_election(false).unwrap();
+            assert_eq!(
+                ink_voting_dapp.get_final_outcome(1).unwrap(),
+                (Vec::new(), 0, true)
+            );
+            assert!(ink_voting_dapp.is_voter_quorum_reached(1));
+        }
+        #[ink::test]
+        fn admin_archive_election_works() {
+            let accounts = ink_env::test::default_accounts::<ink_env::DefaultEnvironment>();
+            let mut ink_voting_dapp = initialize_and_create_election(false).unwrap();
+            ink_voting_dapp.open_election(1).unwrap();
+            vote_as(&mut ink_voting_dapp, accounts.bob, 1, "secondproposal");
+            ink_env::test::set_caller::<ink_env::DefaultEnvironment>(accounts.alice);
+            assert_eq!(
+                ink_voting_dapp.admin_archive_election(1),
+                Err(Error::ElectionNotFinalized)
+            );
+            ink_voting_dapp.close_and_finalize(1).unwrap();
+            ink_env::test::set_caller::<ink_env::DefaultEnvironment>(accounts.bob);
+            assert_eq!(
+                ink_voting_dapp.admin_archive_election(1),
+                Err(Error::OnlyAdmin)
+            );
+            ink_env::test::set_caller::<ink_env::DefaultEnvironment>(accounts.alice);
+            assert_eq!(ink_voting_dapp.admin_archive_election(1), Ok(()));
+            assert!(!ink_voting_dapp.is_account_registered(1, accounts.bob));
+            assert!(!ink_voting_dapp.has_voter_voted(1, accounts.bob));
+            assert_eq!(
+                ink_voting_dapp.get_voter_choice(1, accounts.bob),
+                Vec::new()
+            );
+            assert_eq!(
+                ink_voting_dapp.get_finalized_result(1),
+                (to_ut8("secondproposal"), 1)
+            );
+            let events = ink_env::test::recorded_events().collect::<Vec<_>>();
+            assert!(matches!(
+                decode_event(events.last().unwrap()),
+                Event::Archived(Archived { election_id: 1 })
+            ));
+        }
+        #[ink::test]
+        fn finalized_election_rejects_changes() {
+            let mut ink_voting_dapp = initialize_and_create_election(false).unwrap();
+            let bob = ink_env::test::default_accounts::<ink_env::DefaultEnvironment>().bob;
+            ink_voting_dapp.finalize_election(1).unwrap();
+            assert_eq!(
+                ink_voting_dapp.open_election(1),
+                Err(Error::ElectionFinalized)
+            );
+            assert_eq!(
+                ink_voting_dapp.open_registration(1),
+                Err(Error::ElectionFinalized)
+            );
+            assert_eq!(
+                ink_voting_dapp.vote(1, to_ut8("firstproposal"), 1),
+                Err(Error::ElectionFinalized)
+            );
+            assert_eq!(
+                ink_voting_dapp.delegate_vote(1, bob, 1),
+                Err(Error::ElectionFinalized)
+            );
+            assert_eq!(
+                ink_voting_dapp.remove_proposal(1, to_ut8("firstproposal")),
+                Err(Error::ElectionFinalized)
+            );
+            assert_eq!(
+                ink_voting_dapp.reorder_proposals(1, vec![2, 1]),
+                Err(Error::ElectionFinalized)
+            );
+            assert_eq!(
+                ink_voting_dapp.lock_proposals(1),
+                Err(Error::ElectionFinalized)
+            );
+            assert!(!ink_voting_dapp.is_proposals_locked(1));
+        }
+        #[ink::test]
+        fn delegate_after_finalization_fails() {
+            let accounts = ink_env::test::default_accounts::<ink_env::DefaultEnvironment>();
+            let mut ink_voting_dapp = initialize_and_create_election(false).unwrap();
+            ink_voting_dapp.open_election(1).unwrap();
+            vote_as(&mut ink_voting_dapp, accounts.bob, 1, "firstproposal");
+            ink_env::test::set_caller::<ink_env::DefaultEnvironment>(accounts.alice);
+            ink_voting_dapp.close_and_finalize(1).unwrap();
+            ink_env::test::set_caller::<ink_env::DefaultEnvironment>(accounts.charlie);
+            assert_eq!(
+                ink_voting_dapp.delegate_vote(1, accounts.bob, 1),
+                Err(Error::ElectionFinalized)
+            );
+            assert!(!ink_voting_dapp.is_account_registered(1, accounts.charlie));
+            assert_eq!(ink_voting_dapp.get_voter_weigth(1, accounts.bob), 0);
+        }
+        #[ink::test]
+        fn admin_force_close_election_works() {
+            let accounts = ink_env::test::default_accounts::<ink_env::DefaultEnvironment>();
+            let mut ink_voting_dapp = InkVotingDapp::new();
+            assert_eq!(ink_voting_dapp.get_admin(), accounts.alice);
+            ink_env::test::set_caller::<ink_env::DefaultEnvironment>(accounts.bob);
+            ink_voting_dapp
+                .create_election(to_ut8("bobelection"), false, vec![to_ut8("proposal")])
+                .unwrap();
+            ink_voting_dapp.open_election(1).unwrap();
+            assert_eq!(
+                ink_voting_dapp.admin_force_close_election(1),
+                Err(Error::OnlyAdmin)
+            );
+            ink_env::test::set_caller::<ink_env::DefaultEnvironment>(accounts.alice);
+            assert_eq!(
+                ink_voting_dapp.admin_force_close_election(2),
+                Err(Error::ElectionNotValid)
+            );
+            assert_eq!(ink_voting_dapp.close_election(1), Err(Error::OnlyOwner));
+            assert_eq!(ink_voting_dapp.admin_force_close_election(1), Ok(()));
+            assert!(!ink_voting_dapp.is_election_open(1));
+            let events = ink_env::test::recorded_events().collect::<Vec<_>>();
+            assert_eq!(events.len(), 4);
+            assert!(matches!(decode_event(&events[2]), Event::CloseElection(_)));
+            assert!(matches!(
+                decode_event(&events[3]),
+                Event::AdminForceClosed(AdminForceClosed { election_id: 1, .. })
+            ));
+            assert!(ink_voting_dapp.is_force_closed(1));
+            ink_env::test::set_caller::<ink_env::DefaultEnvironment>(accounts.bob);
+            assert_eq!(
+                ink_voting_dapp.open_election(1),
+                Err(Error::ElectionForceClosed)
+            );
+            assert!(!ink_voting_dapp.is_election_open(1));
+            assert_eq!(ink_voting_dapp.finalize_election(1), Ok(()));
+        }
+        #[ink::test]
+        fn open_registration_works() {
+            let mut ink_voting_dapp = initialize_and_create_election(false).unwrap();
+            assert_eq!(ink_voting_dapp.is_registration_open(1), false);
+            assert_eq!(ink_voting_dapp.open_registration(1), Ok(()));
+            assert_eq!(ink_voting_dapp.is_registration_open(1), true);
+            assert_eq!(
+                ink_voting_dapp.open_registration(2),
+                Err(Error::ElectionNotValid)
+            );
+            let bob = ink_env::test::default_accounts::<ink_env::DefaultEnvironment>().bob;
+            ink_env::test::set_caller::<ink_env::DefaultEnvironment>(bob);
+            assert_eq!(ink_voting_dapp.open_registration(1), Err(Error::OnlyOwner));
+            assert_eq!(ink_env::test::recorded_events().count(), 2);
+        }
+        #[ink::test]
+        fn close_registration_works() {
+            let mut ink_voting_dapp = initialize_and_create_election(false).unwrap();
+            ink_voting_dapp.open_registration(1).unwrap();
+            assert_eq!(ink_voting_dapp.is_registration_open(1), true);
+            assert_eq!(ink_voting_dapp.close_registration(1), Ok(()));
+            assert_eq!(ink_voting_dapp.is_registration_open(1), false);
+            assert_eq!(
+                ink_voting_dapp.open_registration(2),
+                Err(Error::ElectionNotValid)
+            );
+            let bob = ink_env::test::default_accounts::<ink_env::DefaultEnvironment>().bob;
+            ink_env::test::set_caller::<ink_env::DefaultEnvironment>(bob);
+            assert_eq!(ink_voting_dapp.close_registration(1), Err(Error::OnlyOwner));
+            assert_eq!(ink_env::test::recorded_events().count(), 3);
+        }
+        #[ink::test]
+        fn lock_proposals_works() {
+            let mut ink_voting_dapp = initialize_and_create_election(false).unwrap();
+            assert!(!ink_voting_dapp.is_proposals_locked(1));
+            assert_eq!(
+                ink_voting_dapp.lock_proposals(2),
+                Err(Error::ElectionNotValid)
+            );
+            let bob = ink_env::test::default_accounts::<ink_env::DefaultEnvironment>().bob;
+            ink_env::test::set_caller::<ink_env::DefaultEnvironment>(bob);
+            assert_eq!(ink_voting_dapp.lock_proposals(1), Err(Error::OnlyOwner));
+            let alice = ink_env::test::default_accounts::<ink_env::DefaultEnvironment>().alice;
+            ink_env::test::set_caller::<ink_env::DefaultEnvironment>(alice);
+            assert_eq!(ink_voting_dapp.lock_proposals(1), Ok(()));
+            assert!(ink_voting_dapp.is_proposals_locked(1));
+            assert_eq!(
+                ink_voting_dapp.lock_proposals(1),
+                Err(Error::ProposalsLocked)
+            );
+        }
+        #[ink::test]
+        fn open_election_locks_proposals() {
+            let mut ink_voting_dapp = initialize_and_create_election(false).unwrap();
+            ink_voting_dapp.open_election(1).unwrap();
+            assert!(ink_voting_dapp.is_proposals_locked(1));
+            ink_voting_dapp.close_election(1).unwrap();
+            assert!(ink_voting_dapp.is_proposals_locked(1));
+        }
+        #[ink::test]
+        fn close_all_works() {
+            let mut ink_voting_dapp = initialize_and_create_election(false).unwrap();
+            ink_voting_dapp.open_registration(1).unwrap();
+            ink_voting_dapp.open_election(1).unwrap();
+            assert_eq!(ink_voting_dapp.close_all(2), Err(Error::ElectionNotValid));
+            assert_eq!(ink_voting_dapp.close_all(1), Ok(()));
+            assert!(!ink_voting_dapp.is_registration_open(1));
+            assert!(!ink_voting_dapp.is_election_open(1));
+            assert_eq!(ink_env::test::recorded_events().count(), 5);
+            let bob = ink_env::test::default_accounts::<ink_env::DefaultEnvironment>().bob;
+            ink_env::test::set_caller::<ink_env::DefaultEnvironment>(bob);
+            assert_eq!(ink_voting_dapp.close_all(1), Err(Error::OnlyOwner));
+        }
+        #[ink::test]
+        fn get_states_works() {
+            let mut ink_voting_dapp = initialize_and_create_election(false).unwrap();
+            assert_eq!(
+                ink_voting_dapp.get_registration_state(1),
+                RegistrationState::RegistrationClosed
+            );
+            assert_eq!(
+                ink_voting_dapp.get_election_state(1),
+                ElectionState::ElectionClosed
+            );
+            ink_voting_dapp.open_registration(1).unwrap();
+            ink_voting_dapp.open_election(1).unwrap();
+            assert_eq!(
+                ink_voting_dapp.get_registration_state(1),
+                RegistrationState::RegistrationOpen
+            );
+            assert_eq!(
+                ink_voting_dapp.get_election_state(1),
+                ElectionState::ElectionOpen
+            );
+            assert_eq!(
+                ink_voting_dapp.get_election_state(2),
+                ElectionState::ElectionClosed
+            );
+        }
+        #[ink::test]
+        fn change_ownership_works() {
+            let mut ink_voting_dapp = initialize_and_create_election(false).unwrap();
+            let bob = ink_env::test::default_accounts::<ink_env::DefaultEnvironment>().bob;
+            assert_eq!(
+                ink_voting_dapp.change_ownership(2, bob),
+                Err(Error::ElectionNotValid)
+            );
+            let alice = ink_env::test::default_accounts::<ink_env::DefaultEnvironment>().alice;
+            assert_eq!(
+                ink_voting_dapp.change_ownership(1, alice),
+                Err(Error::SameOwner)
+            );
+            assert_eq!(ink_voting_dapp.change_ownership(1, bob), Ok(()));
+            assert_eq!(ink_voting_dapp.get_owner_of_election(1), bob);
+            assert_eq!(
+                ink_voting_dapp.change_ownership(1, bob),
+                Err(Error::OnlyOwner)
+            );
+            assert_eq!(ink_env::test::recorded_events().count(), 2);
+        }
+        #[ink::test]
+        fn two_step_ownership_works() {
+            let mut ink_voting_dapp = initialize_and_create_election(false).unwrap();
+            let accounts = ink_env::test::default_accounts::<ink_env::DefaultEnvironment>();
+            assert_eq!(ink_voting_dapp.propose_new_owner(1, accounts.bob), Ok(()));
+            assert_eq!(ink_voting_dapp.get_owner_of_election(1), accounts.alice);
+            let events = ink_env::test::recorded_events().collect::<Vec<_>>();
+            assert_eq!(events.len(), 2);
+            assert!(matches!(
+                decode_event(&events[1]),
+                Event::OwnershipProposed(OwnershipProposed { election_id: 1, candidate })
+                    if candidate == accounts.bob
+            ));
+            ink_env::test::set_caller::<ink_env::DefaultEnvironment>(accounts.charlie);
+            assert_eq!(
+                ink_voting_dapp.accept_ownership(1),
+                Err(Error::NotPendingOwner)
+            );
+            ink_env::test::set_caller::<ink_env::DefaultEnvironment>(accounts.bob);
+            assert_eq!(ink_voting_dapp.accept_ownership(1), Ok(()));
+            assert_eq!(ink_voting_dapp.get_owner_of_election(1), accounts.bob);
+            let events = ink_env::test::recorded_events().collect::<Vec<_>>();
+            assert_eq!(events.len(), 3);
+            assert!(matches!(
+                decode_event(&events[2]),
+                Event::ChangeOwnership(ChangeOwnership { election_id: 1, new_owner })
+                    if new_owner == accounts.bob
+            ));
+            assert_eq!(
+                ink_voting_dapp.accept_ownership(1),
+                Err(Error::NotPendingOwner)
+            );
+        }
+        #[ink::test]
+        fn owner_change_clears_pending_owner() {
+            let mut ink_voting_dapp = initialize_and_create_election(false).unwrap();
+            ink_voting_dapp
+                .create_election(to_ut8("secondelection"), false, vec![to_ut8("proposal")])
+                .unwrap();
+            let accounts = ink_env::test::default_accounts::<ink_env::DefaultEnvironment>();
+            ink_voting_dapp.propose_new_owner(1, accounts.bob).unwrap();
+            ink_voting_dapp.propose_new_owner(2, accounts.bob).unwrap();
+            ink_voting_dapp
+                .change_ownership(1, accounts.charlie)
+                .unwrap();
+            ink_env::test::set_caller::<ink_env::DefaultEnvironment>(accounts.bob);
+            assert_eq!(
+                ink_voting_dapp.accept_ownership(1),
+                Err(Error::NotPendingOwner)
+            );
+            assert_eq!(ink_voting_dapp.get_owner_of_election(1), accounts.charlie);
+            ink_env::test::set_caller::<ink_env::DefaultEnvironment>(accounts.alice);
+            ink_voting_dapp
+                .transfer_all_ownership(accounts.django)
+                .unwrap();
+            ink_env::test::set_caller::<ink_env::DefaultEnvironment>(accounts.bob);
+            assert_eq!(
+                ink_voting_dapp.accept_ownership(2),
+                Err(Error::NotPendingOwner)
+            );
+            assert_eq!(ink_voting_dapp.get_owner_of_election(2), accounts.django);
+        }
+        #[ink::test]
+        fn transfer_all_ownership_works() {
+            let mut ink_voting_dapp = initialize_and_create_election(false).unwrap();
+            ink_voting_dapp
+                .create_election(to_ut8("secondelection"), false, vec![to_ut8("proposal")])
+                .unwrap();
+            let accounts = ink_env::test::default_accounts::<ink_env::DefaultEnvironment>();
+            assert_eq!(ink_voting_dapp.transfer_all_ownership(accounts.bob), Ok(()));
+            assert_eq!(ink_voting_dapp.get_owner_of_election(1), accounts.bob);
+            assert_eq!(ink_voting_dapp.get_owner_of_election(2), accounts.bob);
+            assert_eq!(ink_env::test::recorded_events().count(), 4);
+            assert_eq!(ink_voting_dapp.transfer_all_ownership(accounts.bob), Ok(()));
+            assert_eq!(ink_env::test::recorded_events().count(), 4);
+            ink_env::test::set_caller::<ink_env::DefaultEnvironment>(accounts.bob);
+            assert_eq!(ink_voting_dapp.open_election(2), Ok(()));
+            assert_eq!(
+                ink_voting_dapp.transfer_all_ownership(accounts.charlie),
+                Ok(())
+            );
+            assert_eq!(ink_voting_dapp.get_owner_of_election(1), accounts.charlie);
+            assert_eq!(ink_voting_dapp.get_owner_of_election(2), accounts.charlie);
+        }
+        #[ink::test]
+        fn transfer_all_ownership_to_self_fails() {
+            let mut ink_voting_dapp = initialize_and_create_election(false).unwrap();
+            let accounts = ink_env::test::default_accounts::<ink_env::DefaultEnvironment>();
+            ink_voting_dapp
+                .propose_new_owner(1, accounts.charlie)
+                .unwrap();
+            assert_eq!(
+                ink_voting_dapp.transfer_all_ownership(accounts.alice),
+                Err(Error::SameOwner)
+            );
+            assert_eq!(ink_voting_dapp.transfer_all_ownership(accounts.bob), Ok(()));
+            ink_env::test::set_caller::<ink_env::DefaultEnvironment>(accounts.charlie);
+            assert_eq!(
+                ink_voting_dapp.accept_ownership(1),
+                Err(Error::NotPendingOwner)
+            );
+            assert_eq!(ink_voting_dapp.get_owner_of_election(1), accounts.bob);
+        }
+        #[ink::test]
+        fn register_works() {
+            let mut ink_voting_dapp = initialize_and_create_election(true).unwrap();
+            let bob = ink_env::test::default_accounts::<ink_env::DefaultEnvironment>().bob;
+            assert_eq!(
+                ink_voting_dapp.register(2, bob),
+                Err(Error::ElectionNotValid)
+            );
+            assert_eq!(
+                ink_voting_dapp.register(1, bob),
+                Err(Error::RegistrationClosed)
+            );
+            ink_voting_dapp.open_registration(1).unwrap();
+            assert_eq!(ink_voting_dapp.register(1, bob), Ok(()));
+            assert_eq!(ink_voting_dapp.is_account_registered(1, bob), true);
+            assert_eq!(ink_voting_dapp.get_voter_weigth(1, bob), 1);
+            assert_eq!(ink_env::test::recorded_events().count(), 3);
+            assert_eq!(
+                ink_voting_dapp.register(1, bob),
+                Err(Error::VoterAlreadyRegistered)
+            );
+        }
+        #[ink::test]
+        fn register_me_works() {
+            let mut ink_voting_dapp = initialize_and_create_election(true).unwrap();
+            assert_eq!(ink_voting_dapp.register_me(2), Err(Error::ElectionNotValid));
+            assert_eq!(
+                ink_voting_dapp.register_me(1),
+                Err(Error::RegistrationClosed)
+            );
+            ink_voting_dapp.open_registration(1).unwrap();
+            assert_eq!(ink_voting_dapp.register_me(1), Ok(()));
+            let alice = ink_env::test::default_accounts::<ink_env::DefaultEnvironment>().alice;
+            assert_eq!(ink_voting_dapp.is_account_registered(1, alice), true);
+            assert_eq!(ink_voting_dapp.get_voter_weigth(1, alice), 1);
+            assert_eq!(ink_env::test::recorded_events().count(), 3);
+            assert_eq!(
+                ink_voting_dapp.register_me(1),
+                Err(Error::VoterAlreadyRegistered)
+            );
+        }
+        #[ink::test]
+        fn register_after_close_and_finalize_fails() {
+            let accounts = ink_env::test::default_accounts::<ink_env::DefaultEnvironment>();
+            let mut ink_voting_dapp = initialize_and_create_election(true).unwrap();
+            ink_voting_dapp.open_registration(1).unwrap();
+            ink_voting_dapp.open_election(1).unwrap();
+            assert_eq!(ink_voting_dapp.register(1, accounts.bob), Ok(()));
+            ink_voting_dapp.close_election(1).unwrap();
+            assert_eq!(
+                ink_voting_dapp.register(1, accounts.charlie),
+                Err(Error::ElectionClosed)
+            );
+            assert_eq!(ink_voting_dapp.register_me(1), Err(Error::ElectionClosed));
+            assert_eq!(
+                ink_voting_dapp.register_batch(1, vec![accounts.charlie]),
+                Err(Error::ElectionClosed)
+            );
+            ink_voting_dapp.finalize_election(1).unwrap();
+            assert_eq!(
+                ink_voting_dapp.register(1, accounts.charlie),
+                Err(Error::ElectionFinalized)
+            );
+            assert_eq!(
+                ink_voting_dapp.register_me(1),
+                Err(Error::ElectionFinalized)
+            );
+            assert_eq!(ink_voting_dapp.get_voter_count(1), 1);
+        }
+        fn hash_leaf(account: &AccountId) -> [u8; 32] {
+            let mut output = [0u8; 32];
+            ink_env::hash_bytes::<ink_env::hash::Blake2x256>(account.as_ref(), &mut output);
+            output
+        }
+        fn hash_pair(a: &[u8; 32], b: &[u8; 32]) -> [u8; 32] {
+            let (first, second) = if a <= b { (a, b) } else { (b, a) };
+            let mut pair = [0u8; 64];
+            pair[..32].copy_from_slice(first);
+            pair[32..].copy_from_slice(second);
+            let mut output = [0u8; 32];
+            ink_env::hash_bytes::<ink_env::hash::Blake2x256>(&pair, &mut output);
+            output
+        }
+        #[ink::test]
+        fn register_with_proof_works() {
+            let accounts = ink_env::test::default_accounts::<ink_env::DefaultEnvironment>();
+            // Allowlist tree over alice, bob, charlie and django.
+            let leaves = [
+                hash_leaf(&accounts.alice),
+                hash_leaf(&accounts.bob),
+                hash_leaf(&accounts.charlie),
+                hash_leaf(&accounts.django),
+            ];
+            let left = hash_pair(&leaves[0], &leaves[1]);
+            let right = hash_pair(&leaves[2], &leaves[3]);
+            let root = hash_pair(&left, &right);
+            let mut ink_voting_dapp = InkVotingDapp::new();
+            ink_voting_dapp
+                .create_election_with_config(
+                    to_ut8("firstelection"),
+                    true,
+                    vec![to_ut8("firstproposal"), to_ut8("secondproposal")],
+                    ElectionConfig {
+                        allowlist_root: Some(root),
+                        ..Default::default()
+                    },
+                )
+                .unwrap();
+            assert_eq!(ink_voting_dapp.get_allowlist_root(1), Some(root));
+            assert_eq!(
+                ink_voting_dapp.register_with_proof(1, vec![leaves[1], right]),
+                Err(Error::RegistrationClosed)
+            );
+            ink_voting_dapp.open_registration(1).unwrap();
+            assert_eq!(ink_voting_dapp.register_me(1), Err(Error::ProofRequired));
+            assert_eq!(
+                ink_voting_dapp.register_with_proof(1, vec![leaves[2], right]),
+                Err(Error::InvalidProof)
+            );
+            assert_eq!(
+                ink_voting_dapp.register_with_proof(1, vec![leaves[1], right]),
+                Ok(())
+            );
+            assert!(ink_voting_dapp.is_account_registered(1, accounts.alice));
+            ink_env::test::set_caller::<ink_env::DefaultEnvironment>(accounts.charlie);
+            assert_eq!(
+                ink_voting_dapp.register_with_proof(1, vec![leaves[3], left]),
+                Ok(())
+            );
+            ink_env::test::set_caller::<ink_env::DefaultEnvironment>(accounts.eve);
+            assert_eq!(
+                ink_voting_dapp.register_with_proof(1, vec![leaves[3], left]),
+                Err(Error::InvalidProof)
+            );
+            assert_eq!(
+                ink_voting_dapp.register_with_proof(2, vec![]),
+                Err(Error::ElectionNotValid)
+            );
+        }
+        #[ink::test]
+        fn self_registration_on_allowlist_works() {
+            let accounts = ink_env::test::default_accounts::<ink_env::DefaultEnvironment>();
+            // Allowlist tree over alice and bob.
+            let leaves = [hash_leaf(&accounts.alice), hash_leaf(&accounts.bob)];
+            let root = hash_pair(&leaves[0], &leaves[1]);
+            let mut ink_voting_dapp = InkVotingDapp::new();
+            ink_voting_dapp
+                .create_election_with_config(
+                    to_ut8("firstelection"),
+                    true,
+                    vec![to_ut8("firstproposal"), to_ut8("secondproposal")],
+                    ElectionConfig {
+                        allowlist_root: Some(root),
+                        ..Default::default()
+                    },
+                )
+                .unwrap();
+            ink_voting_dapp.open_registration(1).unwrap();
+            ink_env::test::set_caller::<ink_env::DefaultEnvironment>(accounts.bob);
+            assert!(ink_voting_dapp.am_i_allowlisted(1, vec![leaves[0]]));
+            assert!(!ink_voting_dapp.am_i_allowlisted(1, vec![leaves[1]]));
+            assert_eq!(ink_voting_dapp.register_me(1), Err(Error::ProofRequired));
+            assert_eq!(
+                ink_voting_dapp.register_with_proof(1, vec![leaves[0]]),
+                Ok(())
+            );
+            ink_env::test::set_caller::<ink_env::DefaultEnvironment>(accounts.eve);
+            assert!(!ink_voting_dapp.am_i_allowlisted(1, vec![leaves[0]]));
+            assert_eq!(ink_voting_dapp.register_me(1), Err(Error::ProofRequired));
+            assert!(!ink_voting_dapp.is_account_registered(1, accounts.eve));
+            ink_env::test::set_caller::<ink_env::DefaultEnvironment>(accounts.alice);
+            assert_eq!(
+                ink_voting_dapp.register(1, accounts.eve),
+                Err(Error::ProofRequired)
+            );
+            assert_eq!(
+                ink_voting_dapp.register_batch(1, vec![accounts.eve]),
+                Err(Error::ProofRequired)
+            );
+        }
+        #[ink::test]
+        fn register_with_proof_without_allowlist_fails() {
+            let mut ink_voting_dapp = initialize_and_create_election(true).unwrap();
+            ink_voting_dapp.open_registration(1).unwrap();
+            assert_eq!(ink_voting_dapp.get_allowlist_root(1), None);
+            assert!(ink_voting_dapp.am_i_allowlisted(1, vec![]));
+            assert_eq!(
+                ink_voting_dapp.register_with_proof(1, vec![]),
+                Err(Error::InvalidProof)
+            );
+        }
+        #[ink::test]
+        fn seed_weights_works() {
+            let accounts = ink_env::test::default_accounts::<ink_env::DefaultEnvironment>();
+            let mut ink_voting_dapp = initialize_and_create_election(true).unwrap();
+            ink_voting_dapp.open_registration(1).unwrap();
+            ink_voting_dapp.register(1, accounts.alice).unwrap();
+            assert_eq!(
+                ink_voting_dapp.seed_weights(2, vec![(accounts.alice, 3)]),
+                Err(Error::ElectionNotValid)
+            );
+            assert_eq!(
+                ink_voting_dapp.seed_weights(1, vec![(accounts.alice, 3), (accounts.bob, 5)]),
+                Ok(())
+            );
+            assert_eq!(ink_voting_dapp.get_voter_weigth(1, accounts.alice), 3);
+            assert_eq!(ink_voting_dapp.get_voter_weigth(1, accounts.bob), 5);
+            assert!(ink_voting_dapp.is_account_registered(1, accounts.bob));
+            assert_eq!(ink_voting_dapp.get_voter_count(1), 2);
+            assert_eq!(ink_env::test::recorded_events().count(), 5);
+            ink_voting_dapp.open_election(1).unwrap();
+            assert_eq!(
+                ink_voting_dapp.seed_weights(1, vec![(accounts.alice, 10)]),
+                Err(Error::ElectionStillOpen)
+            );
+            assert_eq!(ink_voting_dapp.vote(1, to_ut8("firstproposal"), 3), Ok(()));
+            vote_as(&mut ink_voting_dapp, accounts.bob, 1, "secondproposal");
+            assert_eq!(
+                ink_voting_dapp.get_result_election(1).unwrap(),
+                vec![(to_ut8("firstproposal"), 3), (to_ut8("secondproposal"), 1)]
+            );
+            assert_eq!(ink_voting_dapp.get_voter_weigth(1, accounts.bob), 4);
+        }
+        #[ink::test]
+        fn seed_weights_rejects_opened_and_non_owner() {
+            let accounts = ink_env::test::default_accounts::<ink_env::DefaultEnvironment>();
+            let mut ink_voting_dapp = initialize_and_create_election(false).unwrap();
+            ink_voting_dapp
+                .seed_weights(1, vec![(accounts.bob, 10)])
+                .unwrap();
+            ink_voting_dapp.open_election(1).unwrap();
+            ink_env::test::set_caller::<ink_env::DefaultEnvironment>(accounts.bob);
+            ink_voting_dapp.vote(1, to_ut8("firstproposal"), 4).unwrap();
+            ink_env::test::set_caller::<ink_env::DefaultEnvironment>(accounts.alice);
+            ink_voting_dapp.close_election(1).unwrap();
+            assert_eq!(
+                ink_voting_dapp.seed_weights(1, vec![(accounts.bob, 10)]),
+                Err(Error::ElectionAlreadyOpened)
+            );
+            assert_eq!(ink_voting_dapp.get_voter_weigth(1, accounts.bob), 6);
+            assert_eq!(
+                ink_voting_dapp.seed_weights(1, vec![(accounts.charlie, 2)]),
+                Err(Error::ElectionAlreadyOpened)
+            );
+            assert!(!ink_voting_dapp.is_account_registered(1, accounts.charlie));
+            ink_env::test::set_caller::<ink_env::DefaultEnvironment>(accounts.bob);
+            assert_eq!(
+                ink_voting_dapp.seed_weights(1, vec![(accounts.bob, 2)]),
+                Err(Error::OnlyOwner)
+            );
+        }
+        #[ink::test]
+        fn get_my_weight_works() {
+            let accounts = ink_env::test::default_accounts::<ink_env::DefaultEnvironment>();
+            let mut ink_voting_dapp = initialize_and_create_election(false).unwrap();
+            ink_voting_dapp
+                .seed_weights(1, vec![(accounts.bob, 4)])
+                .unwrap();
+            assert_eq!(ink_voting_dapp.get_my_weight(1), 0);
+            ink_env::test::set_caller::<ink_env::DefaultEnvironment>(accounts.bob);
+            assert_eq!(ink_voting_dapp.get_my_weight(1), 4);
+        }
+        #[ink::test]
+        fn vote_without_registration_works() {
+            let mut ink_voting_dapp = initialize_and_create_election(false).unwrap();
+            assert_eq!(
+                ink_voting_dapp.vote(2, to_ut8("firstproposal"), 1),
+                Err(Error::ElectionNotValid)
+            );
+            assert_eq!(
+                ink_voting_dapp.vote(1, to_ut8("firstproposal"), 1),
+                Err(Error::ElectionClosed)
+            );
+            ink_voting_dapp.open_election(1).unwrap();
+            assert_eq!(
+                ink_voting_dapp.vote(1, to_ut8("firstproposal"), 2),
+                Err(Error::VoterHasNotSoMuchWeight)
+            );
+            assert_eq!(
+                ink_voting_dapp.vote(1, to_ut8("inexistentproposal"), 1),
+                Err(Error::InvalidProposal)
+            );
+            assert_eq!(ink_voting_dapp.vote(1, to_ut8("firstproposal"), 1), Ok(()));
+            let alice = ink_env::test::default_accounts::<ink_env::DefaultEnvironment>().alice;
+            assert_eq!(ink_voting_dapp.has_voter_voted(1, alice), true);
+            assert_eq!(ink_voting_dapp.get_voter_weigth(1, alice), 0);
+            let events = ink_env::test::recorded_events().collect::<Vec<_>>();
+            assert_eq!(events.len(), 4);
+            assert!(matches!(
+                decode_event(&events[3]),
+                Event::Voted(Voted { new_total: 1, .. })
+            ));
+            let bob = ink_env::test::default_accounts::<ink_env::DefaultEnvironment>().bob;
+            vote_as(&mut ink_voting_dapp, bob, 1, "firstproposal");
+            let events = ink_env::test::recorded_events().collect::<Vec<_>>();
+            assert!(matches!(
+                decode_event(&events[4]),
+                Event::Voted(Voted { new_total: 2, .. })
+            ));
+            assert_eq!(
+                ink_voting_dapp
+                    .get_votes_proposal(1, to_ut8("firstproposal"))
+                    .unwrap(),
+                2
+            );
+            ink_env::test::set_caller::<ink_env::DefaultEnvironment>(alice);
+            assert_eq!(
+                ink_voting_dapp.vote(1, to_ut8("firstproposal"), 1),
+                Err(Error::VoterHasAlreadyVoted)
+            );
+        }
+        fn sign_vote(
+            secret_key: &secp256k1::SecretKey,
+            election_id: u32,
+            proposal: &str,
+            weight: u128,
+            nonce: u64,
+        ) -> [u8; 65] {
+            sign_vote_for(
+                secret_key,
+                ink_env::account_id::<ink_env::DefaultEnvironment>(),
+                election_id,
+                proposal,
+                weight,
+                nonce,
+            )
+        }
+        fn sign_vote_for(
+            secret_key: &secp256k1::SecretKey,
+            contract: AccountId,
+            election_id: u32,
+            proposal: &str,
+            weight: u128,
+            nonce: u64,
+        ) -> [u8; 65] {
+            let message =
+                scale::Encode::encode(&(contract, election_id, to_ut8(proposal), weight, nonce));
+            let mut message_hash = [0u8; 32];
+            ink_env::hash_bytes::<ink_env::hash::Blake2x256>(&message, &mut message_hash);
+            let (recovery_id, signature) = secp256k1::Secp256k1::new()
+                .sign_ecdsa_recoverable(
+                    &secp256k1::Message::from_slice(&message_hash).unwrap(),
+                    secret_key,
+                )
+                .serialize_compact();
+            let mut result = [0u8; 65];
+            result[..64].copy_from_slice(&signature);
+            result[64] = recovery_id.to_i32() as u8;
+            result
+        }
+        fn signer_account(secret_key: &secp256k1::SecretKey) -> AccountId {
+            let public_key =
+                secp256k1::PublicKey::from_secret_key(&secp256k1::Secp256k1::new(), secret_key);
+            let mut account = [0u8; 32];
+            ink_env::hash_bytes::<ink_env::hash::Blake2x256>(&public_key.serialize(), &mut account);
+            AccountId::from(account)
+        }
+        #[ink::test]
+        fn vote_with_authorization_works() {
+            let mut ink_voting_dapp = initialize_and_create_election(false).unwrap();
+            ink_voting_dapp.open_election(1).unwrap();
+            let secret_key = secp256k1::SecretKey::from_slice(&[7u8; 32]).unwrap();
+            let voter = signer_account(&secret_key);
+            let signature = sign_vote(&secret_key, 1, "firstproposal", 1, 0);
+            // The caller acts as relayer; the vote is counted for the signer.
+            assert_eq!(
+                ink_voting_dapp.vote_with_authorization(
+                    1,
+                    to_ut8("firstproposal"),
+                    1,
+                    voter,
+                    signature
+                ),
+                Ok(())
+            );
+            assert!(ink_voting_dapp.has_voter_voted(1, voter));
+            assert_eq!(ink_voting_dapp.get_authorization_nonce(voter), 1);
+            assert_eq!(
+                ink_voting_dapp
+                    .get_votes_proposal(1, to_ut8("firstproposal"))
+                    .unwrap(),
+                1
+            );
+            let alice = ink_env::test::default_accounts::<ink_env::DefaultEnvironment>().alice;
+            assert!(!ink_voting_dapp.has_voter_voted(1, alice));
+        }
+        #[ink::test]
+        fn vote_with_authorization_rejects_bad_signatures() {
+            let mut ink_voting_dapp = initialize_and_create_election(false).unwrap();
+            ink_voting_dapp.open_election(1).unwrap();
+            let secret_key = secp256k1::SecretKey::from_slice(&[7u8; 32]).unwrap();
+            let voter = signer_account(&secret_key);
+            let bob = ink_env::test::default_accounts::<ink_env::DefaultEnvironment>().bob;
+            let signature = sign_vote(&secret_key, 1, "firstproposal", 1, 0);
+            assert_eq!(
+                ink_voting_dapp.vote_with_authorization(
+                    1,
+                    to_ut8("firstproposal"),
+                    1,
+                    bob,
+                    signature
+                ),
+                Err(Error::SignatureInvalid)
+            );
+            assert_eq!(
+                ink_voting_dapp.vote_with_authorization(
+                    1,
+                    to_ut8("secondproposal"),
+                    1,
+                    voter,
+                    signature
+                ),
+                Err(Error::SignatureInvalid)
+            );
+            let charlie = ink_env::test::default_accounts::<ink_env::DefaultEnvironment>().charlie;
+            let other_contract = sign_vote_for(&secret_key, charlie, 1, "firstproposal", 1, 0);
+            assert_eq!(
+                ink_voting_dapp.vote_with_authorization(
+                    1,
+                    to_ut8("firstproposal"),
+                    1,
+                    voter,
+                    other_contract
+                ),
+                Err(Error::SignatureInvalid)
+            );
+            let stale = sign_vote(&secret_key, 1, "firstproposal", 1, 1);
+            assert_eq!(
+                ink_voting_dapp.vote_with_authorization(
+                    1,
+                    to_ut8("firstproposal"),
+                    1,
+                    voter,
+                    stale
+                ),
+                Err(Error::SignatureInvalid)
+            );
+            assert_eq!(
+                ink_voting_dapp.vote_with_authorization(
+                    1,
+                    to_ut8("firstproposal"),
+                    1,
+                    voter,
+                    signature
+                ),
+                Ok(())
+            );
+            assert_eq!(
+                ink_voting_dapp.vote_with_authorization(
+                    1,
+                    to_ut8("firstproposal"),
+                    1,
+                    voter,
+                    signature
+                ),
+                Err(Error::SignatureInvalid)
+            );
+        }
+        #[ink::test]
+        fn strict_mode_requires_explicit_registration() {
+            let accounts = ink_env::test::default_accounts::<ink_env::DefaultEnvironment>();
+            let mut ink_voting_dapp = initialize_and_create_election(false).unwrap();
+            ink_voting_dapp
+                .create_election_with_config(
+                    to_ut8("strict"),
+                    false,
+                    vec![to_ut8("firstproposal"), to_ut8("secondproposal")],
+                    ElectionConfig {
+                        strict: true,
+                        ..Default::default()
+                    },
+                )
+                .unwrap();
+            ink_voting_dapp.open_election(1).unwrap();
+            ink_voting_dapp.open_election(2).unwrap();
+            assert_eq!(ink_voting_dapp.vote(1, to_ut8("firstproposal"), 1), Ok(()));
+            assert!(ink_voting_dapp.is_account_registered(1, accounts.alice));
+            assert_eq!(
+                ink_voting_dapp.vote(2, to_ut8("firstproposal"), 1),
+                Err(Error::VoterNotRegistred)
+            );
+            assert!(!ink_voting_dapp.is_account_registered(2, accounts.alice));
+            ink_voting_dapp.open_registration(2).unwrap();
+            ink_voting_dapp.register_me(2).unwrap();
+            assert_eq!(ink_voting_dapp.vote(2, to_ut8("firstproposal"), 1), Ok(()));
+        }
+        #[ink::test]
+        fn vote_with_registration_works() {
+            let mut ink_voting_dapp = initialize_and_create_election(true).unwrap();
+            assert_eq!(
+                ink_voting_dapp.vote(2, to_ut8("firstproposal"), 1),
+                Err(Error::ElectionNotValid)
+            );
+            assert_eq!(
+                ink_voting_dapp.vote(1, to_ut8("firstproposal"), 1),
+                Err(Error::ElectionClosed)
+            );
+            ink_voting_dapp.open_election(1).unwrap();
+            assert_eq!(
+                ink_voting_dapp.vote(1, to_ut8("firstproposal"), 1),
+                Err(Error::VoterNotRegistred)
+            );
+            ink_voting_dapp.open_registration(1).unwrap();
+            ink_voting_dapp.register_me(1).unwrap();
+            assert_eq!(
+                ink_voting_dapp.vote(1, to_ut8("firstproposal"), 2),
+                Err(Error::VoterHasNotSoMuchWeight)
+            );
+            assert_eq!(
+                ink_voting_dapp.vote(1, to_ut8("inexistentproposal"), 1),
+                Err(Error::InvalidProposal)
+            );
+            assert_eq!(ink_voting_dapp.vote(1, to_ut8("firstproposal"), 1), Ok(()));
+            let alice = ink_env::test::default_accounts::<ink_env::DefaultEnvironment>().alice;
+            assert_eq!(ink_voting_dapp.has_voter_voted(1, alice), true);
+            assert_eq!(ink_voting_dapp.get_voter_weigth(1, alice), 0);
+            let events = ink_env::test::recorded_events().collect::<Vec<_>>();
+            assert_eq!(events.len(), 6);
+            assert!(matches!(
+                decode_event(&events[5]),
+                Event::Voted(Voted { new_total: 1, .. })
+            ));
+            assert_eq!(
+                ink_voting_dapp
+                    .get_votes_proposal(1, to_ut8("firstproposal"))
+                    .unwrap(),
+                1
+            );
+            assert_eq!(
+                ink_voting_dapp.vote(1, to_ut8("firstproposal"), 1),
+                Err(Error::VoterHasAlreadyVoted)
+            );
+        }
+        #[ink::test]
+        fn get_voter_choice_works() {
+            let accounts = ink_env::test::default_accounts::<ink_env::DefaultEnvironment>();
+            let mut ink_voting_dapp = initialize_and_create_election(false).unwrap();
+            ink_voting_dapp.open_election(1).unwrap();
+            assert_eq!(
+                ink_voting_dapp.get_voter_choice(1, accounts.alice),
+                Vec::<u8>::new()
+            );
+            ink_voting_dapp
+                .vote(1, to_ut8("secondproposal"), 1)
+                .unwrap();
+            assert_eq!(
+                ink_voting_dapp.get_voter_choice(1, accounts.alice),
+                to_ut8("secondproposal")
+            );
+            ink_voting_dapp.revoke_vote(1).unwrap();
+            assert_eq!(
+                ink_voting_dapp.get_voter_choice(1, accounts.alice),
+                Vec::<u8>::new()
+            );
+        }
+        #[ink::test]
+        fn vote_tally_overflow_fails() {
+            let mut ink_voting_dapp = initialize_and_create_election(false).unwrap();
+            let alice = ink_env::test::default_accounts::<ink_env::DefaultEnvironment>().alice;
+            ink_voting_dapp.open_election(1).unwrap();
+            ink_voting_dapp.vote_proposals.insert((1, 1), &u128::MAX);
+            assert_eq!(
+                ink_voting_dapp.vote(1, to_ut8("firstproposal"), 1),
+                Err(Error::TallyOverflow)
+            );
+            assert_eq!(ink_voting_dapp.get_voter_weigth(1, alice), 1);
+            assert!(!ink_voting_dapp.has_voter_voted(1, alice));
+            assert_eq!(
+                ink_voting_dapp
+                    .get_votes_proposal(1, to_ut8("firstproposal"))
+                    .unwrap(),
+                u128::MAX
+            );
+        }
+        #[ink::test]
+        fn revoke_vote_works() {
+            let mut ink_voting_dapp = initialize_and_create_election(false).unwrap();
+            let alice = ink_env::test::default_accounts::<ink_env::DefaultEnvironment>().alice;
+            assert_eq!(ink_voting_dapp.revoke_vote(2), Err(Error::ElectionNotValid));
+            assert_eq!(ink_voting_dapp.revoke_vote(1), Err(Error::ElectionClosed));
+            ink_voting_dapp.open_election(1).unwrap();
+            assert_eq!(ink_voting_dapp.revoke_vote(1), Err(Error::HasNotVoted));
+            ink_voting_dapp.vote(1, to_ut8("firstproposal"), 1).unwrap();
+            assert_eq!(ink_voting_dapp.revoke_vote(1), Ok(()));
+            assert!(!ink_voting_dapp.has_voter_voted(1, alice));
+            assert_eq!(ink_voting_dapp.get_voter_weigth(1, alice), 1);
+            assert_eq!(
+                ink_voting_dapp
+                    .get_votes_proposal(1, to_ut8("firstproposal"))
+                    .unwrap(),
+                0
+            );
+            assert_eq!(ink_env::test::recorded_events().count(), 5);
+            assert_eq!(ink_voting_dapp.revoke_vote(1), Err(Error::HasNotVoted));
+            assert_eq!(ink_voting_dapp.vote(1, to_ut8("secondproposal"), 1), Ok(()));
+            assert_eq!(
+                ink_voting_dapp
+                    .get_votes_proposal(1, to_ut8("secondproposal"))
+                    .unwrap(),
+                1
+            );
+            ink_voting_dapp.close_election(1).unwrap();
+            assert_eq!(ink_voting_dapp.revoke_vote(1), Err(Error::ElectionClosed));
+        }
+        fn create_three_proposal_election(ink_voting_dapp: &mut InkVotingDapp) {
+            ink_voting_dapp
+                .create_election(
+                    to_ut8("threeproposals"),
+                    false,
+                    vec![
+                        to_ut8("firstproposal"),
+                        to_ut8("secondproposal"),
+                        to_ut8("thirdproposal"),
+                    ],
+                )
+                .unwrap();
+        }
+        fn vote_as(
+            ink_voting_dapp: &mut InkVotingDapp,
+            voter: AccountId,
+            election_id: u32,
+            proposal: &str,
+        ) {
+            ink_env::test::set_caller::<ink_env::DefaultEnvironment>(voter);
+            ink_voting_dapp
+                .vote(election_id, to_ut8(proposal), 1)
+                .unwrap();
+        }
+        #[ink::test]
+        fn get_runner_up_works() {
+            let accounts = ink_env::test::default_accounts::<ink_env::DefaultEnvironment>();
+            let mut ink_voting_dapp = InkVotingDapp::new();
+            create_three_proposal_election(&mut ink_voting_dapp);
+            ink_voting_dapp.open_election(1).unwrap();
+            assert_eq!(ink_voting_dapp.get_runner_up(1).unwrap(), (Vec::new(), 0));
+            vote_as(&mut ink_voting_dapp, accounts.alice, 1, "secondproposal");
+            vote_as(&mut ink_voting_dapp, accounts.bob, 1, "secondproposal");
+            vote_as(&mut ink_voting_dapp, accounts.charlie, 1, "secondproposal");
+            vote_as(&mut ink_voting_dapp, accounts.django, 1, "thirdproposal");
+            vote_as(&mut ink_voting_dapp, accounts.eve, 1, "thirdproposal");
+            vote_as(&mut ink_voting_dapp, accounts.frank, 1, "firstproposal");
+            assert_eq!(
+                ink_voting_dapp.get_winner(1).unwrap(),
+                (to_ut8("secondproposal"), 3)
+            );
+            assert_eq!(
+                ink_voting_dapp.get_runner_up(1).unwrap(),
+                (to_ut8("thirdproposal"), 2)
+            );
+        }
+        #[ink::test]
+        fn get_my_receipt_works() {
+            let accounts = ink_env::test::default_accounts::<ink_env::DefaultEnvironment>();
+            let mut ink_voting_dapp = InkVotingDapp::new();
+            create_three_proposal_election(&mut ink_voting_dapp);
+            ink_voting_dapp
+                .seed_weights(1, vec![(accounts.alice, 5)])
+                .unwrap();
+            ink_voting_dapp.open_election(1).unwrap();
+            assert_eq!(ink_voting_dapp.get_my_receipt(1), (false, Vec::new(), 0));
+            ink_voting_dapp
+                .vote(1, to_ut8("secondproposal"), 2)
+                .unwrap();
+            ink_voting_dapp.vote(1, to_ut8("thirdproposal"), 1).unwrap();
+            assert_eq!(
+                ink_voting_dapp.get_my_receipt(1),
+                (true, to_ut8("secondproposal"), 3)
+            );
+            ink_env::test::set_caller::<ink_env::DefaultEnvironment>(accounts.bob);
+            assert_eq!(ink_voting_dapp.get_my_receipt(1), (false, Vec::new(), 0));
+        }
+        #[ink::test]
+        fn rounds_work() {
+            let accounts = ink_env::test::default_accounts::<ink_env::DefaultEnvironment>();
+            let mut ink_voting_dapp = initialize_and_create_election(false).unwrap();
+            ink_voting_dapp.open_election(1).unwrap();
+            assert_eq!(ink_voting_dapp.get_current_round(1), 0);
+            vote_as(&mut ink_voting_dapp, accounts.alice, 1, "firstproposal");
+            vote_as(&mut ink_voting_dapp, accounts.bob, 1, "firstproposal");
+            ink_env::test::set_caller::<ink_env::DefaultEnvironment>(accounts.bob);
+            assert_eq!(ink_voting_dapp.close_round(1), Err(Error::OnlyOwner));
+            ink_env::test::set_caller::<ink_env::DefaultEnvironment>(accounts.alice);
+            assert_eq!(ink_voting_dapp.close_round(1), Ok(()));
+            assert_eq!(ink_voting_dapp.get_current_round(1), 1);
+            assert_eq!(
+                ink_voting_dapp.get_result_election(1).unwrap(),
+                vec![(to_ut8("firstproposal"), 0), (to_ut8("secondproposal"), 0)]
+            );
+            assert!(!ink_voting_dapp.has_voter_voted(1, accounts.bob));
+            vote_as(&mut ink_voting_dapp, accounts.alice, 1, "secondproposal");
+            vote_as(&mut ink_voting_dapp, accounts.bob, 1, "firstproposal");
+            vote_as(&mut ink_voting_dapp, accounts.charlie, 1, "secondproposal");
+            ink_env::test::set_caller::<ink_env::DefaultEnvironment>(accounts.alice);
+            ink_voting_dapp.close_round(1).unwrap();
+            assert_eq!(
+                ink_voting_dapp.get_round_result(1, 0),
+                vec![(to_ut8("firstproposal"), 2), (to_ut8("secondproposal"), 0)]
+            );
+            assert_eq!(
+                ink_voting_dapp.get_round_result(1, 1),
+                vec![(to_ut8("firstproposal"), 1), (to_ut8("secondproposal"), 2)]
+            );
+            assert_eq!(ink_voting_dapp.get_round_result(1, 2), Vec::new());
+        }
+        #[ink::test]
+        fn vote_split_works() {
+            let accounts = ink_env::test::default_accounts::<ink_env::DefaultEnvironment>();
+            let mut ink_voting_dapp = InkVotingDapp::new();
+            create_three_proposal_election(&mut ink_voting_dapp);
+            ink_voting_dapp
+                .seed_weights(1, vec![(accounts.alice, 5)])
+                .unwrap();
+            ink_voting_dapp.open_election(1).unwrap();
+            assert_eq!(
+                ink_voting_dapp.vote_split(
+                    1,
+                    vec![(to_ut8("firstproposal"), 3), (to_ut8("secondproposal"), 1)]
+                ),
+                Ok(())
+            );
+            assert_eq!(
+                ink_voting_dapp
+                    .get_votes_proposal(1, to_ut8("firstproposal"))
+                    .unwrap(),
+                3
+            );
+            assert_eq!(
+                ink_voting_dapp
+                    .get_votes_proposal(1, to_ut8("secondproposal"))
+                    .unwrap(),
+                1
+            );
+            assert!(ink_voting_dapp.has_voter_voted(1, accounts.alice));
+            assert_eq!(ink_voting_dapp.get_voter_weigth(1, accounts.alice), 1);
+            assert_eq!(ink_voting_dapp.get_voted_count(1), 1);
+            assert_eq!(
+                ink_voting_dapp.vote_split(1, vec![(to_ut8("thirdproposal"), 1)]),
+                Err(Error::VoterHasAlreadyVoted)
+            );
+        }
+        #[ink::test]
+        fn vote_split_rejects_version_mismatch() {
+            let accounts = ink_env::test::default_accounts::<ink_env::DefaultEnvironment>();
+            let mut ink_voting_dapp = InkVotingDapp::new();
+            create_three_proposal_election(&mut ink_voting_dapp);
+            ink_voting_dapp
+                .seed_weights(1, vec![(accounts.alice, 5)])
+                .unwrap();
+            ink_voting_dapp.open_election(1).unwrap();
+            ink_voting_dapp.storage_version = STORAGE_VERSION + 1;
+            assert_eq!(
+                ink_voting_dapp.vote_split(1, vec![(to_ut8("firstproposal"), 3)]),
+                Err(Error::VersionMismatch)
+            );
+            assert_eq!(
+                ink_voting_dapp
+                    .get_votes_proposal(1, to_ut8("firstproposal"))
+                    .unwrap(),
+                0
+            );
+            assert!(!ink_voting_dapp.has_voter_voted(1, accounts.alice));
+            assert_eq!(
+                ink_voting_dapp.close_election(1),
+                Err(Error::VersionMismatch)
+            );
+        }
+        #[ink::test]
+        fn vote_split_tally_overflow_changes_nothing() {
+            let accounts = ink_env::test::default_accounts::<ink_env::DefaultEnvironment>();
+            let mut ink_voting_dapp = InkVotingDapp::new();
+            create_three_proposal_election(&mut ink_voting_dapp);
+            ink_voting_dapp
+                .seed_weights(1, vec![(accounts.alice, 100)])
+                .unwrap();
+            ink_voting_dapp.open_election(1).unwrap();
+            ink_voting_dapp.vote_proposals.insert((1, 2), &u128::MAX);
+            assert_eq!(
+                ink_voting_dapp.vote_split(
+                    1,
+                    vec![(to_ut8("firstproposal"), 5), (to_ut8("secondproposal"), 5)]
+                ),
+                Err(Error::TallyOverflow)
+            );
+            assert_eq!(
+                ink_voting_dapp
+                    .get_votes_proposal(1, to_ut8("firstproposal"))
+                    .unwrap(),
+                0
+            );
+            assert_eq!(ink_voting_dapp.get_voter_weigth(1, accounts.alice), 100);
+            assert!(!ink_voting_dapp.has_voter_voted(1, accounts.alice));
+            assert!(ink_voting_dapp
+                .voter_choices
+                .get((1, accounts.alice))
+                .is_none());
+            assert_eq!(ink_env::test::recorded_events().count(), 3);
+        }
+        #[ink::test]
+        fn vote_split_rejects_invalid_allocations() {
+            let accounts = ink_env::test::default_accounts::<ink_env::DefaultEnvironment>();
+            let mut ink_voting_dapp = InkVotingDapp::new();
+            create_three_proposal_election(&mut ink_voting_dapp);
+            ink_voting_dapp
+                .seed_weights(1, vec![(accounts.alice, 3)])
+                .unwrap();
+            ink_voting_dapp.open_election(1).unwrap();
+            assert_eq!(
+                ink_voting_dapp.vote_split(
+                    1,
+                    vec![(to_ut8("firstproposal"), 2), (to_ut8("secondproposal"), 2)]
+                ),
+                Err(Error::WeightExceeded)
+            );
+            assert_eq!(
+                ink_voting_dapp.vote_split(
+                    1,
+                    vec![(to_ut8("firstproposal"), 1), (to_ut8("unknown"), 1)]
+                ),
+                Err(Error::InvalidProposal)
+            );
+            assert_eq!(
+                ink_voting_dapp.vote_split(
+                    1,
+                    vec![(to_ut8("firstproposal"), 1), (to_ut8("firstproposal"), 1)]
+                ),
+                Err(Error::InvalidProposal)
+            );
+            assert_eq!(ink_voting_dapp.get_voter_weigth(1, accounts.alice), 3);
+            assert!(!ink_voting_dapp.has_voter_voted(1, accounts.alice));
+            assert!(ink_voting_dapp
+                .get_result_election(1)
+                .unwrap()
+                .iter()
+                .all(|(_, votes)| *votes == 0));
+        }
+        #[ink::test]
+        fn get_results_vs_supply_works() {
+            let accounts = ink_env::test::default_accounts::<ink_env::DefaultEnvironment>();
+            let mut ink_voting_dapp = InkVotingDapp::new();
+            let proposals = vec![to_ut8("firstproposal"), to_ut8("secondproposal")];
+            ink_voting_dapp
+                .create_election_with_config(
+                    to_ut8("supply"),
+                    false,
+                    proposals.clone(),
+                    ElectionConfig {
+                        total_supply: 8,
+                        ..Default::default()
+                    },
+                )
+                .unwrap();
+            ink_voting_dapp
+                .create_election(to_ut8("nosupply"), false, proposals)
+                .unwrap();
+            ink_voting_dapp.open_election(1).unwrap();
+            ink_voting_dapp.open_election(2).unwrap();
+            for election_id in [1, 2] {
+                vote_as(
+                    &mut ink_voting_dapp,
+                    accounts.alice,
+                    election_id,
+                    "firstproposal",
+                );
+                vote_as(
+                    &mut ink_voting_dapp,
+                    accounts.bob,
+                    election_id,
+                    "firstproposal",
+                );
+                vote_as(
+                    &mut ink_voting_dapp,
+                    accounts.charlie,
+                    election_id,
+                    "secondproposal",
+                );
+                ink_env::test::set_caller::<ink_env::DefaultEnvironment>(accounts.alice);
             }
-            self.proposals_list.insert(&election_id, proposals);
+            assert_eq!(
+                ink_voting_dapp.get_results_vs_supply(1).unwrap(),
+                vec![
+                    (to_ut8("firstproposal"), 2500),
+                    (to_ut8("secondproposal"), 1250)
+                ]
+            );
+            assert_eq!(
+                ink_voting_dapp.get_results_vs_supply(2).unwrap(),
+                vec![
+                    (to_ut8("firstproposal"), 6666),
+                    (to_ut8("secondproposal"), 3333)
+                ]
+            );
         }
-        fn check_election_open(&self, election_id: &u32) -> Result<()> {
-            if !self._is_election_open(election_id) {
-                Err(Error::ElectionClosed)
-            } else {
-                Ok(())
-            }
+        fn create_four_proposal_election(ink_voting_dapp: &mut InkVotingDapp) {
+            ink_voting_dapp
+                .create_election(
+                    to_ut8("fourproposals"),
+                    false,
+                    vec![
+                        to_ut8("firstproposal"),
+                        to_ut8("secondproposal"),
+                        to_ut8("thirdproposal"),
+                        to_ut8("fourthproposal"),
+                    ],
+                )
+                .unwrap();
         }
-        fn check_registration_open(&self, election_id: &u32) -> Result<()> {
-            if !self._is_registration_open(election_id) {
-                Err(Error::RegistrationClosed)
-            } else {
-                Ok(())
-            }
+        #[ink::test]
+        fn get_distribution_works() {
+            let accounts = ink_env::test::default_accounts::<ink_env::DefaultEnvironment>();
+            let mut ink_voting_dapp = InkVotingDapp::new();
+            create_four_proposal_election(&mut ink_voting_dapp);
+            ink_voting_dapp.open_election(1).unwrap();
+            vote_as(&mut ink_voting_dapp, accounts.alice, 1, "fourthproposal");
+            vote_as(&mut ink_voting_dapp, accounts.bob, 1, "fourthproposal");
+            vote_as(&mut ink_voting_dapp, accounts.charlie, 1, "thirdproposal");
+            vote_as(&mut ink_voting_dapp, accounts.django, 1, "secondproposal");
+            assert_eq!(
+                ink_voting_dapp.get_distribution(1).unwrap(),
+                vec![
+                    (to_ut8("fourthproposal"), 2, 1),
+                    (to_ut8("secondproposal"), 1, 2),
+                    (to_ut8("thirdproposal"), 1, 2),
+                    (to_ut8("firstproposal"), 0, 4),
+                ]
+            );
         }
-        fn is_voter_registered(&self, election_id: &u32, voter_address: &AccountId) -> bool {
-            !(self.voters.get((election_id, voter_address)) == None)
+        #[ink::test]
+        fn get_top_proposals_works() {
+            let accounts = ink_env::test::default_accounts::<ink_env::DefaultEnvironment>();
+            let mut ink_voting_dapp = InkVotingDapp::new();
+            create_four_proposal_election(&mut ink_voting_dapp);
+            ink_voting_dapp.open_election(1).unwrap();
+            vote_as(&mut ink_voting_dapp, accounts.alice, 1, "thirdproposal");
+            vote_as(&mut ink_voting_dapp, accounts.bob, 1, "thirdproposal");
+            vote_as(&mut ink_voting_dapp, accounts.charlie, 1, "fourthproposal");
+            assert_eq!(
+                ink_voting_dapp.get_top_proposals(1, 2).unwrap(),
+                vec![(to_ut8("thirdproposal"), 2), (to_ut8("fourthproposal"), 1)]
+            );
+            assert_eq!(ink_voting_dapp.get_top_proposals(1, 10).unwrap().len(), 4);
+            assert_eq!(ink_voting_dapp.get_top_proposals(1, 0).unwrap(), Vec::new());
         }
-
-        fn check_voter_registered(
-            &self,
-            election_id: &u32,
-            voter_address: &AccountId,
-        ) -> Result<()> {
-            if !self.is_voter_registered(election_id, voter_address) {
-                Err(Error::VoterNotRegistred)
-            } else {
-                Ok(())
-            }
+        #[ink::test]
+        fn get_proposal_rank_works() {
+            let accounts = ink_env::test::default_accounts::<ink_env::DefaultEnvironment>();
+            let mut ink_voting_dapp = InkVotingDapp::new();
+            create_three_proposal_election(&mut ink_voting_dapp);
+            ink_voting_dapp.open_election(1).unwrap();
+            vote_as(&mut ink_voting_dapp, accounts.alice, 1, "secondproposal");
+            vote_as(&mut ink_voting_dapp, accounts.bob, 1, "secondproposal");
+            vote_as(&mut ink_voting_dapp, accounts.charlie, 1, "firstproposal");
+            assert_eq!(
+                ink_voting_dapp
+                    .get_proposal_rank(1, to_ut8("secondproposal"))
+                    .unwrap(),
+                1
+            );
+            assert_eq!(
+                ink_voting_dapp
+                    .get_proposal_rank(1, to_ut8("firstproposal"))
+                    .unwrap(),
+                2
+            );
+            assert_eq!(
+                ink_voting_dapp
+                    .get_proposal_rank(1, to_ut8("thirdproposal"))
+                    .unwrap(),
+                3
+            );
+            vote_as(&mut ink_voting_dapp, accounts.django, 1, "thirdproposal");
+            assert_eq!(
+                ink_voting_dapp
+                    .get_proposal_rank(1, to_ut8("firstproposal"))
+                    .unwrap(),
+                2
+            );
+            assert_eq!(
+                ink_voting_dapp
+                    .get_proposal_rank(1, to_ut8("thirdproposal"))
+                    .unwrap(),
+                2
+            );
+            assert_eq!(
+                ink_voting_dapp
+                    .get_proposal_rank(1, to_ut8("unknown"))
+                    .unwrap(),
+                0
+            );
         }
-        fn check_proposal_valid(&self, election_id: &u32, proposal: &Vec<u8>) -> Result<()> {
-            if self.proposals_ids.get((election_id, proposal)) == None {
-                Err(Error::InvalidProposal)
-            } else {
-                Ok(())
+        #[ink::test]
+        fn earliest_first_vote_tie_break_works() {
+            let accounts = ink_env::test::default_accounts::<ink_env::DefaultEnvironment>();
+            let mut ink_voting_dapp = InkVotingDapp::new();
+            let proposals = vec![to_ut8("firstproposal"), to_ut8("secondproposal")];
+            ink_voting_dapp
+                .create_election(to_ut8("firstlisted"), false, proposals.clone())
+                .unwrap();
+            ink_voting_dapp
+                .create_election_with_config(
+                    to_ut8("earliest"),
+                    false,
+                    proposals,
+                    ElectionConfig {
+                        tie_break: TieBreak::EarliestFirstVote,
+                        ..Default::default()
+                    },
+                )
+                .unwrap();
+            ink_voting_dapp.open_election(1).unwrap();
+            ink_voting_dapp.open_election(2).unwrap();
+            for election_id in [1, 2] {
+                vote_as(
+                    &mut ink_voting_dapp,
+                    accounts.alice,
+                    election_id,
+                    "secondproposal",
+                );
             }
-        }
-        fn check_voter_can_vote(
-            &self,
-            election_id: &u32,
-            voter_address: &AccountId,
-            weight: &u128,
-        ) -> Result<()> {
-            let (voter_weight, voter_has_voted) =
-                self.voters.get((election_id, voter_address)).unwrap();
-            if voter_has_voted {
-                Err(Error::VoterHasAlreadyVoted)
-            } else if &voter_weight < weight {
-                Err(Error::VoterHasNotSoMuchWeight)
-            } else {
-                Ok(())
+            // Each block advances the timestamp by 6 ms.
+            ink_env::test::advance_block::<ink_env::DefaultEnvironment>();
+            for election_id in [1, 2] {
+                vote_as(
+                    &mut ink_voting_dapp,
+                    accounts.bob,
+                    election_id,
+                    "firstproposal",
+                );
             }
+            assert_eq!(
+                ink_voting_dapp.get_winner(1).unwrap(),
+                (to_ut8("firstproposal"), 1)
+            );
+            assert_eq!(
+                ink_voting_dapp.get_winner(2).unwrap(),
+                (to_ut8("secondproposal"), 1)
+            );
         }
-        fn _vote(
-            &mut self,
-            election_id: &u32,
-            proposal: &Vec<u8>,
-            voter_address: &AccountId,
-            weight: &u128,
-        ) {
-            let proposal_id = self.proposals_ids.get((election_id, proposal)).unwrap();
-            let vote_proposal = self.vote_proposals.get((election_id, proposal_id)).unwrap();
-            self.vote_proposals
-                .insert((election_id, proposal_id), &(vote_proposal + weight));
-            self.subtract_weight(election_id, voter_address, weight);
-        }
-        fn only_owner(&self, election_id: &u32, address: &AccountId) -> Result<()> {
-            if !self.is_owner(address, election_id) {
-                Err(Error::OnlyOwner)
-            } else {
-                Ok(())
-            }
+        #[ink::test]
+        fn random_tie_break_is_deterministic() {
+            let accounts = ink_env::test::default_accounts::<ink_env::DefaultEnvironment>();
+            let mut ink_voting_dapp = InkVotingDapp::new();
+            ink_voting_dapp
+                .create_election_with_config(
+                    to_ut8("random"),
+                    false,
+                    vec![
+                        to_ut8("firstproposal"),
+                        to_ut8("secondproposal"),
+                        to_ut8("thirdproposal"),
+                    ],
+                    ElectionConfig {
+                        tie_break: TieBreak::Random,
+                        ..Default::default()
+                    },
+                )
+                .unwrap();
+            ink_voting_dapp.open_election(1).unwrap();
+            vote_as(&mut ink_voting_dapp, accounts.alice, 1, "secondproposal");
+            assert_eq!(
+                ink_voting_dapp.get_winner(1).unwrap(),
+                (to_ut8("secondproposal"), 1)
+            );
+            vote_as(&mut ink_voting_dapp, accounts.bob, 1, "thirdproposal");
+            ink_env::test::set_caller::<ink_env::DefaultEnvironment>(accounts.alice);
+            ink_voting_dapp.close_and_finalize(1).unwrap();
+            let (winner, votes) = ink_voting_dapp.get_finalized_result(1);
+            assert_eq!(votes, 1);
+            assert!(winner == to_ut8("secondproposal") || winner == to_ut8("thirdproposal"));
+            // Each block advances the timestamp by 6 ms.
+            ink_env::test::advance_block::<ink_env::DefaultEnvironment>();
+            assert_eq!(ink_voting_dapp.get_winner(1).unwrap(), (winner.clone(), 1));
+            assert_eq!(ink_voting_dapp.get_final_outcome(1).unwrap().0, winner);
         }
-        fn check_if_registration_needed(
-            &mut self,
-            election_id: &u32,
-            voter_address: &AccountId,
-        ) -> Result<()> {
-            if self.elections.get(&election_id).unwrap().1 {
-                self.check_voter_registered(&election_id, &voter_address)?;
-            } else {
-                if !self.is_voter_registered(&election_id, &voter_address) {
-                    self.register_voter(&voter_address, &election_id);
-                }
-            }
-            Ok(())
+        #[ink::test]
+        fn random_pick_uses_seed() {
+            let tied = vec![
+                to_ut8("firstproposal"),
+                to_ut8("secondproposal"),
+                to_ut8("thirdproposal"),
+            ];
+            let mut seed = [0u8; 32];
+            assert_eq!(
+                InkVotingDapp::random_pick(&tied, &seed),
+                to_ut8("firstproposal")
+            );
+            seed[0] = 5;
+            assert_eq!(
+                InkVotingDapp::random_pick(&tied, &seed),
+                to_ut8("thirdproposal")
+            );
+            seed[0] = 1;
+            assert_eq!(
+                InkVotingDapp::random_pick(&tied[1..], &seed),
+                to_ut8("thirdproposal")
+            );
         }
-        fn check_double_election(&self, name: &Vec<u8>) -> Result<()> {
-            if self._election_name_exists(name) {
-                Err(Error::ElectionNotValid)
-            } else {
-                Ok(())
-            }
+        #[ink::test]
+        fn get_winning_margin_works() {
+            let accounts = ink_env::test::default_accounts::<ink_env::DefaultEnvironment>();
+            let mut ink_voting_dapp = InkVotingDapp::new();
+            create_three_proposal_election(&mut ink_voting_dapp);
+            ink_voting_dapp.open_election(1).unwrap();
+            vote_as(&mut ink_voting_dapp, accounts.alice, 1, "secondproposal");
+            vote_as(&mut ink_voting_dapp, accounts.bob, 1, "firstproposal");
+            assert_eq!(ink_voting_dapp.get_winning_margin(1).unwrap(), 0);
+            vote_as(&mut ink_voting_dapp, accounts.charlie, 1, "secondproposal");
+            vote_as(&mut ink_voting_dapp, accounts.django, 1, "secondproposal");
+            assert_eq!(ink_voting_dapp.get_winning_margin(1).unwrap(), 2);
         }
-    }
-
-    /// Unit tests in Rust are normally defined within such a `#[cfg(test)]`
-    /// module and test functions are marked with a `#[test]` attribute.
-    /// The below code is technically just normal Rust code.
-    #[cfg(test)]
-    mod tests {
-        /// Imports all the definitions from the outer scope so we can use them here.
-        use super::*;
-
-        /// Imports `ink_lang` so we can use `#[ink::test]`.
-        use ink_lang as ink;
-
-        /// We test if the default constructor does its job.
         #[ink::test]
-        fn new_works() {
-            let ink_voting_dapp = InkVotingDapp::new();
-            assert_eq!(ink_voting_dapp.get_number_elections(), 0);
+        fn get_winning_margin_with_single_proposal_works() {
+            let accounts = ink_env::test::default_accounts::<ink_env::DefaultEnvironment>();
+            let mut ink_voting_dapp = InkVotingDapp::new();
+            ink_voting_dapp
+                .create_election(to_ut8("single"), false, vec![to_ut8("onlyproposal")])
+                .unwrap();
+            ink_voting_dapp.open_election(1).unwrap();
+            assert_eq!(ink_voting_dapp.get_winning_margin(1).unwrap(), 0);
+            vote_as(&mut ink_voting_dapp, accounts.alice, 1, "onlyproposal");
+            vote_as(&mut ink_voting_dapp, accounts.bob, 1, "onlyproposal");
+            assert_eq!(ink_voting_dapp.get_winning_margin(1).unwrap(), 2);
         }
-
-        fn to_ut8(string: &str) -> Vec<u8> {
-            string.as_bytes().to_vec()
+        #[ink::test]
+        fn get_runner_up_with_tie_works() {
+            let accounts = ink_env::test::default_accounts::<ink_env::DefaultEnvironment>();
+            let mut ink_voting_dapp = InkVotingDapp::new();
+            create_three_proposal_election(&mut ink_voting_dapp);
+            ink_voting_dapp.open_election(1).unwrap();
+            vote_as(&mut ink_voting_dapp, accounts.alice, 1, "thirdproposal");
+            vote_as(&mut ink_voting_dapp, accounts.bob, 1, "secondproposal");
+            vote_as(&mut ink_voting_dapp, accounts.charlie, 1, "firstproposal");
+            vote_as(&mut ink_voting_dapp, accounts.django, 1, "thirdproposal");
+            vote_as(&mut ink_voting_dapp, accounts.eve, 1, "secondproposal");
+            assert_eq!(
+                ink_voting_dapp.get_winner(1).unwrap(),
+                (to_ut8("secondproposal"), 2)
+            );
+            assert_eq!(
+                ink_voting_dapp.get_runner_up(1).unwrap(),
+                (to_ut8("thirdproposal"), 2)
+            );
         }
-        fn initialize_and_create_election(require_registration: bool) -> Result<InkVotingDapp> {
+        #[ink::test]
+        fn get_runner_up_single_proposal_works() {
             let mut ink_voting_dapp = InkVotingDapp::new();
-            ink_voting_dapp.create_election(
-                to_ut8("firstelection"),
-                require_registration,
-                vec![to_ut8("firstproposal"), to_ut8("secondproposal")],
-            )?;
-            Ok(ink_voting_dapp)
+            ink_voting_dapp
+                .create_election(to_ut8("single"), false, vec![to_ut8("onlyproposal")])
+                .unwrap();
+            ink_voting_dapp.open_election(1).unwrap();
+            ink_voting_dapp.vote(1, to_ut8("onlyproposal"), 1).unwrap();
+            assert_eq!(
+                ink_voting_dapp.get_winner(1).unwrap(),
+                (to_ut8("onlyproposal"), 1)
+            );
+            assert_eq!(ink_voting_dapp.get_runner_up(1).unwrap(), (Vec::new(), 0));
         }
-
-        /// We test a simple use case of our contract.
         #[ink::test]
-        fn create_election_works() {
+        fn motion_passes() {
+            let accounts = ink_env::test::default_accounts::<ink_env::DefaultEnvironment>();
             let mut ink_voting_dapp = InkVotingDapp::new();
-            //let accounts = ink_env::test::default_accounts::<ink_env::DefaultEnvironment>();
             assert_eq!(
-                ink_voting_dapp.create_election(
-                    to_ut8("firstelection"),
-                    false,
-                    vec![to_ut8("firstproposal"), to_ut8("secondproposal")]
-                ),
+                ink_voting_dapp.create_motion(to_ut8("motion"), false),
                 Ok(())
             );
+            assert!(ink_voting_dapp.is_motion(1));
             assert_eq!(
-                ink_voting_dapp.create_election(
-                    to_ut8("firstelection"),
+                ink_voting_dapp.get_proposal_for_election(to_ut8("motion")),
+                vec![to_ut8("For"), to_ut8("Against")]
+            );
+            ink_voting_dapp.open_election(1).unwrap();
+            vote_as(&mut ink_voting_dapp, accounts.alice, 1, "For");
+            vote_as(&mut ink_voting_dapp, accounts.bob, 1, "For");
+            vote_as(&mut ink_voting_dapp, accounts.charlie, 1, "Against");
+            assert_eq!(ink_voting_dapp.get_motion_result(1).unwrap(), (2, 1, true));
+            assert_eq!(ink_voting_dapp.get_winner(1).unwrap(), (to_ut8("For"), 1));
+        }
+        #[ink::test]
+        fn motion_fails() {
+            let accounts = ink_env::test::default_accounts::<ink_env::DefaultEnvironment>();
+            let mut ink_voting_dapp = InkVotingDapp::new();
+            ink_voting_dapp
+                .create_motion(to_ut8("motion"), false)
+                .unwrap();
+            ink_voting_dapp.open_election(1).unwrap();
+            vote_as(&mut ink_voting_dapp, accounts.alice, 1, "For");
+            vote_as(&mut ink_voting_dapp, accounts.bob, 1, "Against");
+            assert_eq!(ink_voting_dapp.get_motion_result(1).unwrap(), (1, 1, false));
+            assert_eq!(ink_voting_dapp.get_winner(1).unwrap(), (Vec::new(), 0));
+            vote_as(&mut ink_voting_dapp, accounts.charlie, 1, "Against");
+            vote_as(&mut ink_voting_dapp, accounts.django, 1, "Against");
+            assert_eq!(ink_voting_dapp.get_motion_result(1).unwrap(), (1, 3, false));
+            assert_eq!(
+                ink_voting_dapp.get_winner(1).unwrap(),
+                (to_ut8("Against"), 2)
+            );
+        }
+        #[ink::test]
+        fn motion_result_of_regular_election_is_empty() {
+            let ink_voting_dapp = initialize_and_create_election(false).unwrap();
+            assert!(!ink_voting_dapp.is_motion(1));
+            assert_eq!(ink_voting_dapp.get_motion_result(1).unwrap(), (0, 0, false));
+        }
+        #[ink::test]
+        fn get_eligible_weight_works() {
+            let accounts = ink_env::test::default_accounts::<ink_env::DefaultEnvironment>();
+            let mut ink_voting_dapp = initialize_and_create_election(true).unwrap();
+            ink_voting_dapp.open_registration(1).unwrap();
+            ink_voting_dapp.register(1, accounts.alice).unwrap();
+            ink_voting_dapp.register(1, accounts.bob).unwrap();
+            ink_voting_dapp
+                .seed_weights(1, vec![(accounts.charlie, 3)])
+                .unwrap();
+            assert_eq!(ink_voting_dapp.get_eligible_weight(1), 0);
+            ink_voting_dapp.open_election(1).unwrap();
+            assert_eq!(ink_voting_dapp.get_eligible_weight(1), 5);
+            ink_voting_dapp.vote(1, to_ut8("firstproposal"), 1).unwrap();
+            assert_eq!(ink_voting_dapp.get_eligible_weight(1), 5);
+        }
+        #[ink::test]
+        fn get_abstentions_works() {
+            let accounts = ink_env::test::default_accounts::<ink_env::DefaultEnvironment>();
+            let mut ink_voting_dapp = initialize_and_create_election(true).unwrap();
+            ink_voting_dapp.open_registration(1).unwrap();
+            ink_voting_dapp.register(1, accounts.alice).unwrap();
+            ink_voting_dapp.register(1, accounts.bob).unwrap();
+            ink_voting_dapp.open_election(1).unwrap();
+            assert_eq!(ink_voting_dapp.get_voter_count(1), 2);
+            assert_eq!(ink_voting_dapp.get_abstentions(1), 2);
+            ink_voting_dapp.vote(1, to_ut8("firstproposal"), 1).unwrap();
+            assert_eq!(ink_voting_dapp.get_voted_count(1), 1);
+            assert_eq!(ink_voting_dapp.get_abstentions(1), 1);
+            ink_voting_dapp.revoke_vote(1).unwrap();
+            assert_eq!(ink_voting_dapp.get_voted_count(1), 0);
+            assert_eq!(ink_voting_dapp.get_abstentions(1), 2);
+            assert_eq!(ink_voting_dapp.get_abstentions(2), 0);
+        }
+        #[ink::test]
+        fn proposal_caps_work() {
+            let accounts = ink_env::test::default_accounts::<ink_env::DefaultEnvironment>();
+            let mut ink_voting_dapp = InkVotingDapp::new();
+            ink_voting_dapp
+                .create_election_with_config(
+                    to_ut8("capped"),
                     false,
-                    vec![to_ut8("firstproposal")]
-                ),
-                Err(Error::ElectionNotValid)
+                    vec![to_ut8("firstproposal"), to_ut8("secondproposal")],
+                    ElectionConfig {
+                        caps: vec![2, 0],
+                        ..Default::default()
+                    },
+                )
+                .unwrap();
+            assert_eq!(
+                ink_voting_dapp.get_proposal_cap(1, to_ut8("firstproposal")),
+                2
             );
             assert_eq!(
-                ink_voting_dapp.create_election(to_ut8("secondelection"), false, vec![]),
-                Err(Error::InsufficientProposals)
+                ink_voting_dapp.get_proposal_cap(1, to_ut8("secondproposal")),
+                0
             );
-            assert_eq!(ink_voting_dapp.get_number_elections(), 1);
-            assert_eq!(ink_env::test::recorded_events().count(), 1);
+            ink_voting_dapp.open_election(1).unwrap();
+            vote_as(&mut ink_voting_dapp, accounts.alice, 1, "firstproposal");
+            vote_as(&mut ink_voting_dapp, accounts.bob, 1, "firstproposal");
+            ink_env::test::set_caller::<ink_env::DefaultEnvironment>(accounts.charlie);
             assert_eq!(
-                ink_voting_dapp.get_proposal_for_election(to_ut8("firstelection")),
-                vec![to_ut8("firstproposal"), to_ut8("secondproposal")]
+                ink_voting_dapp.vote(1, to_ut8("firstproposal"), 1),
+                Err(Error::ProposalCapReached)
             );
+            assert!(!ink_voting_dapp.has_voter_voted(1, accounts.charlie));
             assert_eq!(
-                ink_voting_dapp.get_owner_of_election(1),
-                ink_env::test::default_accounts::<ink_env::DefaultEnvironment>().alice
+                ink_voting_dapp
+                    .get_votes_proposal(1, to_ut8("firstproposal"))
+                    .unwrap(),
+                2
             );
+            assert_eq!(ink_voting_dapp.vote(1, to_ut8("secondproposal"), 1), Ok(()));
         }
         #[ink::test]
-        fn open_election_works() {
-            let mut ink_voting_dapp = initialize_and_create_election(false).unwrap();
-            assert_eq!(ink_voting_dapp.is_election_open(1), false);
-            assert_eq!(ink_voting_dapp.open_election(1), Ok(()));
-            assert_eq!(ink_voting_dapp.is_election_open(1), true);
+        fn mismatched_caps_are_rejected() {
+            let mut ink_voting_dapp = InkVotingDapp::new();
             assert_eq!(
-                ink_voting_dapp.open_election(2),
-                Err(Error::ElectionNotValid)
+                ink_voting_dapp.create_election_with_config(
+                    to_ut8("capped"),
+                    false,
+                    vec![to_ut8("firstproposal"), to_ut8("secondproposal")],
+                    ElectionConfig {
+                        caps: vec![2],
+                        ..Default::default()
+                    },
+                ),
+                Err(Error::InvalidCaps)
             );
-            let bob = ink_env::test::default_accounts::<ink_env::DefaultEnvironment>().bob;
-            ink_env::test::set_caller::<ink_env::DefaultEnvironment>(bob);
-            assert_eq!(ink_voting_dapp.open_election(1), Err(Error::OnlyOwner));
-            assert_eq!(ink_env::test::recorded_events().count(), 2);
+            assert!(ink_voting_dapp.is_name_available(to_ut8("capped")));
+        }
+        fn create_writein_election(ink_voting_dapp: &mut InkVotingDapp, proposals: Vec<Vec<u8>>) {
+            ink_voting_dapp
+                .create_election_with_config(
+                    to_ut8("writeins"),
+                    false,
+                    proposals,
+                    ElectionConfig {
+                        allow_writeins: true,
+                        ..Default::default()
+                    },
+                )
+                .unwrap();
         }
         #[ink::test]
-        fn close_election_works() {
-            let mut ink_voting_dapp = initialize_and_create_election(false).unwrap();
+        fn single_vote_rejects_vote_split() {
+            let accounts = ink_env::test::default_accounts::<ink_env::DefaultEnvironment>();
+            let mut ink_voting_dapp = InkVotingDapp::new();
+            ink_voting_dapp
+                .create_election_with_config(
+                    to_ut8("single"),
+                    false,
+                    vec![to_ut8("firstproposal"), to_ut8("secondproposal")],
+                    ElectionConfig {
+                        single_vote: true,
+                        ..Default::default()
+                    },
+                )
+                .unwrap();
+            ink_voting_dapp
+                .seed_weights(1, vec![(accounts.alice, 5)])
+                .unwrap();
             ink_voting_dapp.open_election(1).unwrap();
-            assert_eq!(ink_voting_dapp.is_election_open(1), true);
-            assert_eq!(ink_voting_dapp.close_election(1), Ok(()));
-            assert_eq!(ink_voting_dapp.is_election_open(1), false);
             assert_eq!(
-                ink_voting_dapp.open_election(2),
-                Err(Error::ElectionNotValid)
+                ink_voting_dapp.vote_split(
+                    1,
+                    vec![(to_ut8("firstproposal"), 2), (to_ut8("secondproposal"), 1)]
+                ),
+                Err(Error::SplitNotAllowed)
             );
-            let bob = ink_env::test::default_accounts::<ink_env::DefaultEnvironment>().bob;
-            ink_env::test::set_caller::<ink_env::DefaultEnvironment>(bob);
-            assert_eq!(ink_voting_dapp.close_election(1), Err(Error::OnlyOwner));
-            assert_eq!(ink_env::test::recorded_events().count(), 3);
+            assert_eq!(
+                ink_voting_dapp.vote_split(1, vec![(to_ut8("firstproposal"), 5)]),
+                Err(Error::SplitNotAllowed)
+            );
+            assert_eq!(ink_voting_dapp.get_voter_weigth(1, accounts.alice), 5);
+            assert!(!ink_voting_dapp.has_voter_voted(1, accounts.alice));
         }
         #[ink::test]
-        fn open_registration_works() {
-            let mut ink_voting_dapp = initialize_and_create_election(false).unwrap();
-            assert_eq!(ink_voting_dapp.is_registration_open(1), false);
-            assert_eq!(ink_voting_dapp.open_registration(1), Ok(()));
-            assert_eq!(ink_voting_dapp.is_registration_open(1), true);
+        fn single_vote_spends_full_weight() {
+            let accounts = ink_env::test::default_accounts::<ink_env::DefaultEnvironment>();
+            let mut ink_voting_dapp = InkVotingDapp::new();
+            ink_voting_dapp
+                .create_election_with_config(
+                    to_ut8("single"),
+                    false,
+                    vec![to_ut8("firstproposal"), to_ut8("secondproposal")],
+                    ElectionConfig {
+                        single_vote: true,
+                        ..Default::default()
+                    },
+                )
+                .unwrap();
+            ink_voting_dapp
+                .seed_weights(1, vec![(accounts.alice, 3), (accounts.bob, 2)])
+                .unwrap();
+            ink_voting_dapp.open_election(1).unwrap();
             assert_eq!(
-                ink_voting_dapp.open_registration(2),
-                Err(Error::ElectionNotValid)
+                ink_voting_dapp.vote(1, to_ut8("firstproposal"), 1),
+                Err(Error::WeightMustMatchFull)
+            );
+            assert_eq!(ink_voting_dapp.vote(1, to_ut8("firstproposal"), 0), Ok(()));
+            assert!(ink_voting_dapp.has_voter_voted(1, accounts.alice));
+            assert_eq!(ink_voting_dapp.get_voter_weigth(1, accounts.alice), 0);
+            assert_eq!(
+                ink_voting_dapp
+                    .get_votes_proposal(1, to_ut8("firstproposal"))
+                    .unwrap(),
+                3
+            );
+            ink_env::test::set_caller::<ink_env::DefaultEnvironment>(accounts.bob);
+            assert_eq!(
+                ink_voting_dapp.vote(1, to_ut8("secondproposal"), 100),
+                Err(Error::WeightMustMatchFull)
+            );
+            assert_eq!(ink_voting_dapp.vote(1, to_ut8("secondproposal"), 2), Ok(()));
+            assert!(ink_voting_dapp.has_voter_voted(1, accounts.bob));
+            assert_eq!(
+                ink_voting_dapp
+                    .get_votes_proposal(1, to_ut8("secondproposal"))
+                    .unwrap(),
+                2
             );
-            let bob = ink_env::test::default_accounts::<ink_env::DefaultEnvironment>().bob;
-            ink_env::test::set_caller::<ink_env::DefaultEnvironment>(bob);
-            assert_eq!(ink_voting_dapp.open_registration(1), Err(Error::OnlyOwner));
-            assert_eq!(ink_env::test::recorded_events().count(), 2);
         }
         #[ink::test]
-        fn close_registration_works() {
+        fn effective_weight_preview_works() {
+            let accounts = ink_env::test::default_accounts::<ink_env::DefaultEnvironment>();
             let mut ink_voting_dapp = initialize_and_create_election(false).unwrap();
-            ink_voting_dapp.open_registration(1).unwrap();
-            assert_eq!(ink_voting_dapp.is_registration_open(1), true);
-            assert_eq!(ink_voting_dapp.close_registration(1), Ok(()));
-            assert_eq!(ink_voting_dapp.is_registration_open(1), false);
+            ink_voting_dapp
+                .create_election_with_config(
+                    to_ut8("decay"),
+                    false,
+                    vec![to_ut8("firstproposal"), to_ut8("secondproposal")],
+                    ElectionConfig {
+                        voting_period: 60,
+                        decay: true,
+                        ..Default::default()
+                    },
+                )
+                .unwrap();
+            ink_voting_dapp.open_election(1).unwrap();
+            ink_voting_dapp.open_election(2).unwrap();
+            assert_eq!(ink_voting_dapp.effective_weight(2, accounts.bob, 100), 100);
+            // Each block advances the timestamp by 6 ms.
+            for _ in 0..5 {
+                ink_env::test::advance_block::<ink_env::DefaultEnvironment>();
+            }
+            assert_eq!(ink_voting_dapp.effective_weight(2, accounts.bob, 100), 50);
+            assert_eq!(ink_voting_dapp.effective_weight(1, accounts.bob, 100), 100);
+            for _ in 0..5 {
+                ink_env::test::advance_block::<ink_env::DefaultEnvironment>();
+            }
+            assert_eq!(ink_voting_dapp.effective_weight(2, accounts.bob, 100), 0);
+            assert_eq!(ink_voting_dapp.effective_weight(1, accounts.bob, 100), 100);
+        }
+        #[ink::test]
+        fn decaying_weight_works() {
+            let accounts = ink_env::test::default_accounts::<ink_env::DefaultEnvironment>();
+            let mut ink_voting_dapp = InkVotingDapp::new();
+            ink_voting_dapp
+                .create_election_with_config(
+                    to_ut8("decay"),
+                    false,
+                    vec![to_ut8("firstproposal"), to_ut8("secondproposal")],
+                    ElectionConfig {
+                        voting_period: 60,
+                        decay: true,
+                        ..Default::default()
+                    },
+                )
+                .unwrap();
+            ink_voting_dapp
+                .seed_weights(
+                    1,
+                    vec![
+                        (accounts.alice, 100),
+                        (accounts.bob, 100),
+                        (accounts.charlie, 100),
+                    ],
+                )
+                .unwrap();
+            ink_voting_dapp.open_election(1).unwrap();
+            ink_voting_dapp
+                .vote(1, to_ut8("firstproposal"), 100)
+                .unwrap();
             assert_eq!(
-                ink_voting_dapp.open_registration(2),
-                Err(Error::ElectionNotValid)
+                ink_voting_dapp
+                    .get_votes_proposal(1, to_ut8("firstproposal"))
+                    .unwrap(),
+                100
+            );
+            // Each block advances the timestamp by 6 ms.
+            for _ in 0..5 {
+                ink_env::test::advance_block::<ink_env::DefaultEnvironment>();
+            }
+            ink_env::test::set_caller::<ink_env::DefaultEnvironment>(accounts.bob);
+            ink_voting_dapp
+                .vote(1, to_ut8("secondproposal"), 100)
+                .unwrap();
+            assert_eq!(
+                ink_voting_dapp
+                    .get_votes_proposal(1, to_ut8("secondproposal"))
+                    .unwrap(),
+                50
+            );
+            assert_eq!(ink_voting_dapp.get_voter_weigth(1, accounts.bob), 0);
+            for _ in 0..4 {
+                ink_env::test::advance_block::<ink_env::DefaultEnvironment>();
+            }
+            ink_env::test::set_caller::<ink_env::DefaultEnvironment>(accounts.charlie);
+            ink_voting_dapp
+                .vote(1, to_ut8("secondproposal"), 100)
+                .unwrap();
+            assert_eq!(
+                ink_voting_dapp
+                    .get_votes_proposal(1, to_ut8("secondproposal"))
+                    .unwrap(),
+                60
             );
-            let bob = ink_env::test::default_accounts::<ink_env::DefaultEnvironment>().bob;
-            ink_env::test::set_caller::<ink_env::DefaultEnvironment>(bob);
-            assert_eq!(ink_voting_dapp.close_registration(1), Err(Error::OnlyOwner));
-            assert_eq!(ink_env::test::recorded_events().count(), 3);
         }
         #[ink::test]
-        fn change_ownership_works() {
+        fn can_vote_reports_election_state() {
+            let accounts = ink_env::test::default_accounts::<ink_env::DefaultEnvironment>();
             let mut ink_voting_dapp = initialize_and_create_election(false).unwrap();
-            let bob = ink_env::test::default_accounts::<ink_env::DefaultEnvironment>().bob;
+            ink_voting_dapp
+                .create_election_with_config(
+                    to_ut8("delayed"),
+                    false,
+                    vec![to_ut8("firstproposal")],
+                    ElectionConfig {
+                        voting_delay: 6,
+                        ..Default::default()
+                    },
+                )
+                .unwrap();
             assert_eq!(
-                ink_voting_dapp.change_ownership(2, bob),
+                ink_voting_dapp.can_vote(9, accounts.bob, 1),
                 Err(Error::ElectionNotValid)
             );
-            assert_eq!(ink_voting_dapp.change_ownership(1, bob), Ok(()));
-            assert_eq!(ink_voting_dapp.get_owner_of_election(1), bob);
             assert_eq!(
-                ink_voting_dapp.change_ownership(1, bob),
-                Err(Error::OnlyOwner)
+                ink_voting_dapp.can_vote(1, accounts.bob, 1),
+                Err(Error::ElectionClosed)
+            );
+            ink_voting_dapp.open_election(1).unwrap();
+            ink_voting_dapp.open_election(2).unwrap();
+            assert_eq!(
+                ink_voting_dapp.can_vote(2, accounts.bob, 1),
+                Err(Error::VotingNotStarted)
+            );
+            // Each block advances the timestamp by 6 ms.
+            ink_env::test::advance_block::<ink_env::DefaultEnvironment>();
+            assert_eq!(ink_voting_dapp.can_vote(2, accounts.bob, 1), Ok(()));
+            assert_eq!(ink_voting_dapp.can_vote(1, accounts.bob, 1), Ok(()));
+            assert!(!ink_voting_dapp.is_voter_registered(&1, &accounts.bob));
+            assert_eq!(ink_voting_dapp.get_voter_count(1), 0);
+            ink_voting_dapp.close_and_finalize(1).unwrap();
+            assert_eq!(
+                ink_voting_dapp.can_vote(1, accounts.bob, 1),
+                Err(Error::ElectionFinalized)
             );
-            assert_eq!(ink_env::test::recorded_events().count(), 2);
         }
         #[ink::test]
-        fn register_works() {
+        fn can_vote_reports_voter_state() {
+            let accounts = ink_env::test::default_accounts::<ink_env::DefaultEnvironment>();
             let mut ink_voting_dapp = initialize_and_create_election(true).unwrap();
-            let bob = ink_env::test::default_accounts::<ink_env::DefaultEnvironment>().bob;
+            let proposals = vec![to_ut8("firstproposal"), to_ut8("secondproposal")];
+            ink_voting_dapp
+                .create_election_with_config(
+                    to_ut8("limited"),
+                    false,
+                    proposals.clone(),
+                    ElectionConfig {
+                        max_voters: 1,
+                        ..Default::default()
+                    },
+                )
+                .unwrap();
+            ink_voting_dapp
+                .create_election_with_config(
+                    to_ut8("single"),
+                    false,
+                    proposals,
+                    ElectionConfig {
+                        single_vote: true,
+                        ..Default::default()
+                    },
+                )
+                .unwrap();
+            ink_voting_dapp
+                .seed_weights(2, vec![(accounts.alice, 3)])
+                .unwrap();
+            ink_voting_dapp
+                .seed_weights(3, vec![(accounts.alice, 3)])
+                .unwrap();
+            for election_id in 1..=3 {
+                ink_voting_dapp.open_election(election_id).unwrap();
+            }
             assert_eq!(
-                ink_voting_dapp.register(2, bob),
-                Err(Error::ElectionNotValid)
+                ink_voting_dapp.can_vote(1, accounts.bob, 1),
+                Err(Error::VoterNotRegistred)
             );
             assert_eq!(
-                ink_voting_dapp.register(1, bob),
-                Err(Error::RegistrationClosed)
+                ink_voting_dapp.can_vote(2, accounts.bob, 1),
+                Err(Error::VoterLimitReached)
             );
-            ink_voting_dapp.open_registration(1).unwrap();
-            assert_eq!(ink_voting_dapp.register(1, bob), Ok(()));
-            assert_eq!(ink_voting_dapp.is_account_registered(1, bob), true);
-            assert_eq!(ink_voting_dapp.get_voter_weigth(1, bob), 1);
-            assert_eq!(ink_env::test::recorded_events().count(), 3);
             assert_eq!(
-                ink_voting_dapp.register(1, bob),
-                Err(Error::VoterAlreadyRegistered)
+                ink_voting_dapp.can_vote(2, accounts.alice, 4),
+                Err(Error::VoterHasNotSoMuchWeight)
             );
-        }
-        #[ink::test]
-        fn register_me_works() {
-            let mut ink_voting_dapp = initialize_and_create_election(true).unwrap();
-            assert_eq!(ink_voting_dapp.register_me(2), Err(Error::ElectionNotValid));
             assert_eq!(
-                ink_voting_dapp.register_me(1),
-                Err(Error::RegistrationClosed)
+                ink_voting_dapp.can_vote(3, accounts.alice, 2),
+                Err(Error::WeightMustMatchFull)
             );
-            ink_voting_dapp.open_registration(1).unwrap();
-            assert_eq!(ink_voting_dapp.register_me(1), Ok(()));
-            let alice = ink_env::test::default_accounts::<ink_env::DefaultEnvironment>().alice;
-            assert_eq!(ink_voting_dapp.is_account_registered(1, alice), true);
-            assert_eq!(ink_voting_dapp.get_voter_weigth(1, alice), 1);
-            assert_eq!(ink_env::test::recorded_events().count(), 3);
+            assert_eq!(ink_voting_dapp.can_vote(3, accounts.alice, 0), Ok(()));
+            ink_voting_dapp.set_max_vote_weight(2).unwrap();
             assert_eq!(
-                ink_voting_dapp.register_me(1),
-                Err(Error::VoterAlreadyRegistered)
+                ink_voting_dapp.can_vote(2, accounts.alice, 3),
+                Err(Error::WeightTooLarge)
+            );
+            assert_eq!(ink_voting_dapp.can_vote(2, accounts.alice, 2), Ok(()));
+            ink_voting_dapp.set_max_vote_weight(0).unwrap();
+            ink_voting_dapp.vote(3, to_ut8("firstproposal"), 0).unwrap();
+            assert_eq!(
+                ink_voting_dapp.can_vote(3, accounts.alice, 0),
+                Err(Error::VoterHasAlreadyVoted)
             );
         }
         #[ink::test]
-        fn vote_without_registration_works() {
+        fn can_vote_matches_vote() {
+            let accounts = ink_env::test::default_accounts::<ink_env::DefaultEnvironment>();
             let mut ink_voting_dapp = initialize_and_create_election(false).unwrap();
+            ink_voting_dapp.open_election(1).unwrap();
+            ink_env::test::set_caller::<ink_env::DefaultEnvironment>(accounts.bob);
             assert_eq!(
-                ink_voting_dapp.vote(2, to_ut8("firstproposal"), 1),
-                Err(Error::ElectionNotValid)
+                ink_voting_dapp.can_vote(1, accounts.bob, 2),
+                Err(Error::VoterHasNotSoMuchWeight)
             );
             assert_eq!(
-                ink_voting_dapp.vote(1, to_ut8("firstproposal"), 1),
-                Err(Error::ElectionClosed)
+                ink_voting_dapp.vote(1, to_ut8("firstproposal"), 2),
+                Err(Error::VoterHasNotSoMuchWeight)
             );
+            assert!(!ink_voting_dapp.is_voter_registered(&1, &accounts.bob));
+            assert_eq!(ink_voting_dapp.get_voter_count(1), 0);
+            assert_eq!(ink_voting_dapp.can_vote(1, accounts.bob, 1), Ok(()));
+            assert_eq!(ink_voting_dapp.vote(1, to_ut8("firstproposal"), 1), Ok(()));
+        }
+        #[ink::test]
+        fn voting_delay_works() {
+            let mut ink_voting_dapp = InkVotingDapp::new();
+            ink_voting_dapp
+                .create_election_with_config(
+                    to_ut8("delayed"),
+                    false,
+                    vec![to_ut8("firstproposal"), to_ut8("secondproposal")],
+                    ElectionConfig {
+                        voting_delay: 12,
+                        ..Default::default()
+                    },
+                )
+                .unwrap();
             ink_voting_dapp.open_election(1).unwrap();
             assert_eq!(
-                ink_voting_dapp.vote(1, to_ut8("firstproposal"), 2),
-                Err(Error::VoterHasNotSoMuchWeight)
+                ink_voting_dapp.vote(1, to_ut8("firstproposal"), 1),
+                Err(Error::VotingNotStarted)
             );
+            // Each block advances the timestamp by 6 ms.
+            ink_env::test::advance_block::<ink_env::DefaultEnvironment>();
             assert_eq!(
-                ink_voting_dapp.vote(1, to_ut8("inexistentproposal"), 1),
-                Err(Error::InvalidProposal)
+                ink_voting_dapp.vote_split(1, vec![(to_ut8("firstproposal"), 1)]),
+                Err(Error::VotingNotStarted)
             );
+            ink_env::test::advance_block::<ink_env::DefaultEnvironment>();
             assert_eq!(ink_voting_dapp.vote(1, to_ut8("firstproposal"), 1), Ok(()));
-            let alice = ink_env::test::default_accounts::<ink_env::DefaultEnvironment>().alice;
-            assert_eq!(ink_voting_dapp.has_voter_voted(1, alice), true);
-            assert_eq!(ink_voting_dapp.get_voter_weigth(1, alice), 0);
-            assert_eq!(ink_env::test::recorded_events().count(), 3);
+        }
+        #[ink::test]
+        fn reopening_keeps_first_opened_at() {
+            let accounts = ink_env::test::default_accounts::<ink_env::DefaultEnvironment>();
+            let mut ink_voting_dapp = InkVotingDapp::new();
+            ink_voting_dapp
+                .create_election_with_config(
+                    to_ut8("delayed"),
+                    false,
+                    vec![to_ut8("firstproposal"), to_ut8("secondproposal")],
+                    ElectionConfig {
+                        voting_delay: 6,
+                        ..Default::default()
+                    },
+                )
+                .unwrap();
+            ink_voting_dapp.open_election(1).unwrap();
+            let window = ink_voting_dapp.get_voting_window(1);
+            // Each block advances the timestamp by 6 ms.
+            ink_env::test::advance_block::<ink_env::DefaultEnvironment>();
+            assert_eq!(ink_voting_dapp.can_vote(1, accounts.bob, 1), Ok(()));
             assert_eq!(
-                ink_voting_dapp.get_votes_proposal(1, to_ut8("firstproposal")),
-                1
+                ink_voting_dapp.open_election(1),
+                Err(Error::ElectionStillOpen)
             );
+            assert_eq!(ink_voting_dapp.can_vote(1, accounts.bob, 1), Ok(()));
+            ink_voting_dapp.close_election(1).unwrap();
+            ink_voting_dapp.open_election(1).unwrap();
+            assert_eq!(ink_voting_dapp.get_voting_window(1), window);
+            assert_eq!(ink_voting_dapp.can_vote(1, accounts.bob, 1), Ok(()));
+        }
+        #[ink::test]
+        fn early_bonus_works() {
+            let accounts = ink_env::test::default_accounts::<ink_env::DefaultEnvironment>();
+            let mut ink_voting_dapp = InkVotingDapp::new();
+            ink_voting_dapp
+                .create_election_with_config(
+                    to_ut8("early"),
+                    false,
+                    vec![to_ut8("firstproposal"), to_ut8("secondproposal")],
+                    ElectionConfig {
+                        early_bonus_bps: 5_000,
+                        early_window: 10,
+                        ..Default::default()
+                    },
+                )
+                .unwrap();
+            ink_voting_dapp
+                .seed_weights(1, vec![(accounts.alice, 2), (accounts.bob, 2)])
+                .unwrap();
+            ink_voting_dapp.open_election(1).unwrap();
+            ink_voting_dapp.vote(1, to_ut8("firstproposal"), 2).unwrap();
             assert_eq!(
-                ink_voting_dapp.vote(1, to_ut8("firstproposal"), 1),
-                Err(Error::VoterHasAlreadyVoted)
+                ink_voting_dapp
+                    .get_votes_proposal(1, to_ut8("firstproposal"))
+                    .unwrap(),
+                3
+            );
+            assert_eq!(ink_voting_dapp.get_voter_weigth(1, accounts.alice), 0);
+            // Each block advances the timestamp by 6 ms.
+            ink_env::test::advance_block::<ink_env::DefaultEnvironment>();
+            ink_env::test::advance_block::<ink_env::DefaultEnvironment>();
+            ink_env::test::set_caller::<ink_env::DefaultEnvironment>(accounts.bob);
+            ink_voting_dapp
+                .vote(1, to_ut8("secondproposal"), 2)
+                .unwrap();
+            assert_eq!(
+                ink_voting_dapp
+                    .get_votes_proposal(1, to_ut8("secondproposal"))
+                    .unwrap(),
+                2
             );
         }
         #[ink::test]
-        fn vote_with_registration_works() {
-            let mut ink_voting_dapp = initialize_and_create_election(true).unwrap();
+        fn proposal_cap_counts_early_bonus() {
+            let accounts = ink_env::test::default_accounts::<ink_env::DefaultEnvironment>();
+            let mut ink_voting_dapp = InkVotingDapp::new();
+            ink_voting_dapp
+                .create_election_with_config(
+                    to_ut8("capped"),
+                    false,
+                    vec![to_ut8("firstproposal"), to_ut8("secondproposal")],
+                    ElectionConfig {
+                        caps: vec![10, 0],
+                        early_bonus_bps: 10_000,
+                        early_window: 10,
+                        ..Default::default()
+                    },
+                )
+                .unwrap();
+            ink_voting_dapp
+                .seed_weights(1, vec![(accounts.alice, 10), (accounts.bob, 10)])
+                .unwrap();
+            ink_voting_dapp.open_election(1).unwrap();
             assert_eq!(
-                ink_voting_dapp.vote(2, to_ut8("firstproposal"), 1),
-                Err(Error::ElectionNotValid)
+                ink_voting_dapp.vote(1, to_ut8("firstproposal"), 6),
+                Err(Error::ProposalCapReached)
+            );
+            assert_eq!(
+                ink_voting_dapp.vote_split(
+                    1,
+                    vec![(to_ut8("firstproposal"), 6), (to_ut8("secondproposal"), 1)]
+                ),
+                Err(Error::ProposalCapReached)
             );
+            assert_eq!(ink_voting_dapp.vote(1, to_ut8("firstproposal"), 5), Ok(()));
+            ink_env::test::set_caller::<ink_env::DefaultEnvironment>(accounts.bob);
             assert_eq!(
                 ink_voting_dapp.vote(1, to_ut8("firstproposal"), 1),
-                Err(Error::ElectionClosed)
+                Err(Error::ProposalCapReached)
+            );
+            assert_eq!(
+                ink_voting_dapp
+                    .get_votes_proposal(1, to_ut8("firstproposal"))
+                    .unwrap(),
+                10
             );
+        }
+        #[ink::test]
+        fn early_bonus_revoke_refunds_spent_weight() {
+            let accounts = ink_env::test::default_accounts::<ink_env::DefaultEnvironment>();
+            let mut ink_voting_dapp = InkVotingDapp::new();
+            ink_voting_dapp
+                .create_election_with_config(
+                    to_ut8("early"),
+                    false,
+                    vec![to_ut8("firstproposal"), to_ut8("secondproposal")],
+                    ElectionConfig {
+                        early_bonus_bps: 5_000,
+                        early_window: 10,
+                        ..Default::default()
+                    },
+                )
+                .unwrap();
+            ink_voting_dapp
+                .seed_weights(1, vec![(accounts.alice, 10_000)])
+                .unwrap();
+            ink_voting_dapp.open_election(1).unwrap();
+            for _ in 0..3 {
+                ink_voting_dapp
+                    .vote(1, to_ut8("firstproposal"), 10_000)
+                    .unwrap();
+                assert_eq!(
+                    ink_voting_dapp
+                        .get_votes_proposal(1, to_ut8("firstproposal"))
+                        .unwrap(),
+                    15_000
+                );
+                assert_eq!(
+                    ink_voting_dapp.get_my_receipt(1),
+                    (true, to_ut8("firstproposal"), 10_000)
+                );
+                ink_voting_dapp.revoke_vote(1).unwrap();
+                assert_eq!(ink_voting_dapp.get_voter_weigth(1, accounts.alice), 10_000);
+                assert_eq!(
+                    ink_voting_dapp
+                        .get_votes_proposal(1, to_ut8("firstproposal"))
+                        .unwrap(),
+                    0
+                );
+            }
+            ink_voting_dapp
+                .vote(1, to_ut8("firstproposal"), 10_000)
+                .unwrap();
+            ink_voting_dapp.close_round(1).unwrap();
+            assert_eq!(ink_voting_dapp.get_voter_weigth(1, accounts.alice), 10_000);
+        }
+        #[ink::test]
+        fn anonymized_votes_hide_the_voter() {
+            let accounts = ink_env::test::default_accounts::<ink_env::DefaultEnvironment>();
+            let mut ink_voting_dapp = InkVotingDapp::new();
+            let salt = [9u8; 32];
+            ink_voting_dapp
+                .create_election_with_config(
+                    to_ut8("anonymous"),
+                    false,
+                    vec![to_ut8("firstproposal"), to_ut8("secondproposal")],
+                    ElectionConfig {
+                        anonymize: true,
+                        salt,
+                        ..Default::default()
+                    },
+                )
+                .unwrap();
+            ink_voting_dapp.open_election(1).unwrap();
+            vote_as(&mut ink_voting_dapp, accounts.bob, 1, "secondproposal");
+            let mut input = [0u8; 64];
+            input[..32].copy_from_slice(accounts.bob.as_ref());
+            input[32..].copy_from_slice(&salt);
+            let mut expected_hash = [0u8; 32];
+            ink_env::hash_bytes::<ink_env::hash::Blake2x256>(&input, &mut expected_hash);
+            let events = ink_env::test::recorded_events().collect::<Vec<_>>();
+            assert!(!events
+                .iter()
+                .any(|event| matches!(decode_event(event), Event::Voted(_))));
+            match decode_event(events.last().unwrap()) {
+                Event::AnonymousVoted(AnonymousVoted {
+                    election_id,
+                    voter_hash,
+                    new_total,
+                    ..
+                }) => {
+                    assert_eq!(election_id, 1);
+                    assert_eq!(voter_hash, expected_hash);
+                    assert_eq!(new_total, 1);
+                }
+                _ => panic!("expected AnonymousVoted as the last event"),
+            }
+            assert!(ink_voting_dapp.has_voter_voted(1, accounts.bob));
+            ink_voting_dapp.revoke_vote(1).unwrap();
+            let events = ink_env::test::recorded_events().collect::<Vec<_>>();
+            assert!(!events
+                .iter()
+                .any(|event| matches!(decode_event(event), Event::VoteRevoked(_))));
+            match decode_event(events.last().unwrap()) {
+                Event::AnonymousVoteRevoked(AnonymousVoteRevoked {
+                    election_id,
+                    voter_hash,
+                    weight,
+                }) => {
+                    assert_eq!(election_id, 1);
+                    assert_eq!(voter_hash, expected_hash);
+                    assert_eq!(weight, 1);
+                }
+                _ => panic!("expected AnonymousVoteRevoked as the last event"),
+            }
+        }
+        #[ink::test]
+        fn writein_works() {
+            let accounts = ink_env::test::default_accounts::<ink_env::DefaultEnvironment>();
+            let mut ink_voting_dapp = InkVotingDapp::new();
+            create_writein_election(&mut ink_voting_dapp, vec![to_ut8("firstproposal")]);
             ink_voting_dapp.open_election(1).unwrap();
+            vote_as(&mut ink_voting_dapp, accounts.alice, 1, "writein");
             assert_eq!(
-                ink_voting_dapp.vote(1, to_ut8("firstproposal"), 1),
-                Err(Error::VoterNotRegistred)
+                ink_voting_dapp.get_proposal_for_election(to_ut8("writeins")),
+                vec![to_ut8("firstproposal"), to_ut8("writein")]
             );
-            ink_voting_dapp.open_registration(1).unwrap();
-            ink_voting_dapp.register_me(1).unwrap();
             assert_eq!(
-                ink_voting_dapp.vote(1, to_ut8("firstproposal"), 2),
-                Err(Error::VoterHasNotSoMuchWeight)
+                ink_voting_dapp
+                    .get_votes_proposal(1, to_ut8("writein"))
+                    .unwrap(),
+                1
             );
+            vote_as(&mut ink_voting_dapp, accounts.bob, 1, "writein");
             assert_eq!(
-                ink_voting_dapp.vote(1, to_ut8("inexistentproposal"), 1),
+                ink_voting_dapp
+                    .get_votes_proposal(1, to_ut8("writein"))
+                    .unwrap(),
+                2
+            );
+            assert_eq!(
+                ink_voting_dapp.get_proposal_for_election(to_ut8("writeins")),
+                vec![to_ut8("firstproposal"), to_ut8("writein")]
+            );
+            let write_ins = ink_env::test::recorded_events()
+                .filter(|event| matches!(decode_event(event), Event::WriteInAdded(_)))
+                .count();
+            assert_eq!(write_ins, 1);
+            assert_eq!(
+                ink_voting_dapp.get_winner(1).unwrap(),
+                (to_ut8("writein"), 2)
+            );
+            ink_env::test::set_caller::<ink_env::DefaultEnvironment>(accounts.charlie);
+            assert_eq!(
+                ink_voting_dapp.vote(1, Vec::new(), 1),
                 Err(Error::InvalidProposal)
             );
-            assert_eq!(ink_voting_dapp.vote(1, to_ut8("firstproposal"), 1), Ok(()));
-            let alice = ink_env::test::default_accounts::<ink_env::DefaultEnvironment>().alice;
-            assert_eq!(ink_voting_dapp.has_voter_voted(1, alice), true);
-            assert_eq!(ink_voting_dapp.get_voter_weigth(1, alice), 0);
-            assert_eq!(ink_env::test::recorded_events().count(), 5);
+        }
+        #[ink::test]
+        fn writein_respects_max_proposals() {
+            let mut ink_voting_dapp = InkVotingDapp::new();
+            create_writein_election(
+                &mut ink_voting_dapp,
+                (0..MAX_PROPOSALS as u8).map(|i| vec![i]).collect(),
+            );
+            ink_voting_dapp.open_election(1).unwrap();
             assert_eq!(
-                ink_voting_dapp.get_votes_proposal(1, to_ut8("firstproposal")),
-                1
+                ink_voting_dapp.vote(1, to_ut8("writein"), 1),
+                Err(Error::TooManyProposals)
             );
+            let alice = ink_env::test::default_accounts::<ink_env::DefaultEnvironment>().alice;
+            assert!(!ink_voting_dapp.has_voter_voted(1, alice));
+        }
+        #[ink::test]
+        fn create_election_rejects_too_many_proposals() {
+            let mut ink_voting_dapp = InkVotingDapp::new();
             assert_eq!(
-                ink_voting_dapp.vote(1, to_ut8("firstproposal"), 1),
-                Err(Error::VoterHasAlreadyVoted)
+                ink_voting_dapp.create_election(
+                    to_ut8("toomany"),
+                    false,
+                    (0..=MAX_PROPOSALS as u8).map(|i| vec![i]).collect()
+                ),
+                Err(Error::TooManyProposals)
             );
         }
         #[ink::test]
+        fn voting_started_emitted_once() {
+            let accounts = ink_env::test::default_accounts::<ink_env::DefaultEnvironment>();
+            let mut ink_voting_dapp = initialize_and_create_election(false).unwrap();
+            ink_voting_dapp.open_election(1).unwrap();
+            vote_as(&mut ink_voting_dapp, accounts.alice, 1, "firstproposal");
+            vote_as(&mut ink_voting_dapp, accounts.bob, 1, "secondproposal");
+            vote_as(&mut ink_voting_dapp, accounts.charlie, 1, "firstproposal");
+            let voting_started = ink_env::test::recorded_events()
+                .filter(|event| matches!(decode_event(event), Event::VotingStarted(_)))
+                .count();
+            assert_eq!(voting_started, 1);
+        }
+        #[ink::test]
+        fn get_participation_rate_works() {
+            let accounts = ink_env::test::default_accounts::<ink_env::DefaultEnvironment>();
+            let mut ink_voting_dapp = initialize_and_create_election(true).unwrap();
+            assert_eq!(ink_voting_dapp.get_participation_rate(1), 0);
+            ink_voting_dapp.open_registration(1).unwrap();
+            ink_voting_dapp.register(1, accounts.alice).unwrap();
+            ink_voting_dapp.register(1, accounts.bob).unwrap();
+            ink_voting_dapp.register(1, accounts.charlie).unwrap();
+            ink_voting_dapp.open_election(1).unwrap();
+            assert_eq!(ink_voting_dapp.get_participation_rate(1), 0);
+            vote_as(&mut ink_voting_dapp, accounts.alice, 1, "firstproposal");
+            assert_eq!(ink_voting_dapp.get_participation_rate(1), 3333);
+            vote_as(&mut ink_voting_dapp, accounts.bob, 1, "firstproposal");
+            assert_eq!(ink_voting_dapp.get_participation_rate(1), 6666);
+            vote_as(&mut ink_voting_dapp, accounts.charlie, 1, "firstproposal");
+            assert_eq!(ink_voting_dapp.get_participation_rate(1), 10_000);
+        }
+        #[ink::test]
         fn delegate_vote_without_registration_works() {
             let mut ink_voting_dapp = initialize_and_create_election(false).unwrap();
             let bob = ink_env::test::default_accounts::<ink_env::DefaultEnvironment>().bob;
@@ -847,6 +6571,11 @@ mod ink_voting_dapp {
                 ink_voting_dapp.delegate_vote(2, bob, 1),
                 Err(Error::ElectionNotValid)
             );
+            assert_eq!(
+                ink_voting_dapp.delegate_vote(1, bob, 1),
+                Err(Error::ElectionClosed)
+            );
+            ink_voting_dapp.open_election(1).unwrap();
             assert_eq!(
                 ink_voting_dapp.delegate_vote(1, bob, 2),
                 Err(Error::VoterHasNotSoMuchWeight)
@@ -856,13 +6585,145 @@ mod ink_voting_dapp {
             assert_eq!(ink_voting_dapp.get_voter_weigth(1, alice), 0);
             assert_eq!(ink_voting_dapp.has_voter_voted(1, bob), false);
             assert_eq!(ink_voting_dapp.get_voter_weigth(1, bob), 2);
-            assert_eq!(ink_env::test::recorded_events().count(), 2);
+            assert_eq!(ink_env::test::recorded_events().count(), 3);
+            assert_eq!(ink_voting_dapp.get_delegated_weight(1, bob), 1);
+            assert_eq!(ink_voting_dapp.get_delegated_weight(1, alice), 0);
+            assert_eq!(
+                ink_voting_dapp.get_voter_weigth(1, bob)
+                    - ink_voting_dapp.get_delegated_weight(1, bob),
+                1
+            );
             assert_eq!(
                 ink_voting_dapp.delegate_vote(1, bob, 1),
                 Err(Error::VoterHasAlreadyVoted)
             );
         }
         #[ink::test]
+        fn get_total_remaining_weight_works() {
+            let accounts = ink_env::test::default_accounts::<ink_env::DefaultEnvironment>();
+            let mut ink_voting_dapp = initialize_and_create_election(false).unwrap();
+            create_three_proposal_election(&mut ink_voting_dapp);
+            assert_eq!(ink_voting_dapp.get_total_remaining_weight(accounts.bob), 0);
+            ink_voting_dapp
+                .seed_weights(1, vec![(accounts.bob, 3)])
+                .unwrap();
+            ink_voting_dapp
+                .seed_weights(2, vec![(accounts.bob, 5)])
+                .unwrap();
+            assert_eq!(ink_voting_dapp.get_total_remaining_weight(accounts.bob), 8);
+            ink_voting_dapp.open_election(2).unwrap();
+            ink_env::test::set_caller::<ink_env::DefaultEnvironment>(accounts.bob);
+            ink_voting_dapp.vote(2, to_ut8("firstproposal"), 4).unwrap();
+            assert_eq!(ink_voting_dapp.get_total_remaining_weight(accounts.bob), 4);
+        }
+        #[ink::test]
+        fn audit_weight_is_conserved() {
+            let accounts = ink_env::test::default_accounts::<ink_env::DefaultEnvironment>();
+            let mut ink_voting_dapp = InkVotingDapp::new();
+            create_three_proposal_election(&mut ink_voting_dapp);
+            ink_voting_dapp
+                .seed_weights(
+                    1,
+                    vec![
+                        (accounts.alice, 5),
+                        (accounts.bob, 3),
+                        (accounts.charlie, 2),
+                    ],
+                )
+                .unwrap();
+            assert_eq!(ink_voting_dapp.audit_weight(1), 10);
+            ink_voting_dapp.open_election(1).unwrap();
+            ink_voting_dapp.vote(1, to_ut8("firstproposal"), 2).unwrap();
+            assert_eq!(ink_voting_dapp.audit_weight(1), 10);
+            ink_voting_dapp.delegate_vote(1, accounts.bob, 3).unwrap();
+            assert_eq!(ink_voting_dapp.audit_weight(1), 10);
+            ink_env::test::set_caller::<ink_env::DefaultEnvironment>(accounts.bob);
+            ink_voting_dapp
+                .vote(1, to_ut8("secondproposal"), 4)
+                .unwrap();
+            ink_env::test::set_caller::<ink_env::DefaultEnvironment>(accounts.charlie);
+            ink_voting_dapp.delegate_vote(1, accounts.bob, 2).unwrap();
+            assert_eq!(ink_voting_dapp.audit_weight(1), 10);
+            ink_env::test::set_caller::<ink_env::DefaultEnvironment>(accounts.alice);
+            ink_voting_dapp.revoke_vote(1).unwrap();
+            assert_eq!(ink_voting_dapp.audit_weight(1), 10);
+        }
+        #[ink::test]
+        fn partial_delegation_then_vote_works() {
+            let accounts = ink_env::test::default_accounts::<ink_env::DefaultEnvironment>();
+            let mut ink_voting_dapp = initialize_and_create_election(false).unwrap();
+            ink_voting_dapp
+                .seed_weights(1, vec![(accounts.alice, 3), (accounts.bob, 1)])
+                .unwrap();
+            ink_voting_dapp.open_election(1).unwrap();
+            ink_voting_dapp.delegate_vote(1, accounts.bob, 1).unwrap();
+            assert!(!ink_voting_dapp.has_voter_voted(1, accounts.alice));
+            assert_eq!(ink_voting_dapp.get_voter_weigth(1, accounts.alice), 2);
+            assert_eq!(ink_voting_dapp.get_voter_weigth(1, accounts.bob), 2);
+            ink_voting_dapp.vote(1, to_ut8("firstproposal"), 2).unwrap();
+            assert!(ink_voting_dapp.has_voter_voted(1, accounts.alice));
+            assert_eq!(ink_voting_dapp.get_voter_weigth(1, accounts.alice), 0);
+            vote_as(&mut ink_voting_dapp, accounts.bob, 1, "secondproposal");
+            assert!(!ink_voting_dapp.has_voter_voted(1, accounts.bob));
+            assert_eq!(
+                ink_voting_dapp.get_result_election(1).unwrap(),
+                vec![(to_ut8("firstproposal"), 2), (to_ut8("secondproposal"), 1)]
+            );
+        }
+        #[ink::test]
+        fn delegate_vote_overflow_fails() {
+            let mut ink_voting_dapp = initialize_and_create_election(false).unwrap();
+            ink_voting_dapp.open_election(1).unwrap();
+            let accounts = ink_env::test::default_accounts::<ink_env::DefaultEnvironment>();
+            ink_voting_dapp
+                .voters
+                .insert((1, accounts.bob), &(u128::MAX - 1, false));
+            ink_voting_dapp
+                .voters
+                .insert((1, accounts.alice), &(2, false));
+            assert_eq!(
+                ink_voting_dapp.delegate_vote(1, accounts.bob, 2),
+                Err(Error::WeightOverflow)
+            );
+            assert_eq!(ink_voting_dapp.get_voter_weigth(1, accounts.alice), 2);
+            assert_eq!(
+                ink_voting_dapp.get_voter_weigth(1, accounts.bob),
+                u128::MAX - 1
+            );
+            assert_eq!(ink_voting_dapp.delegate_vote(1, accounts.bob, 1), Ok(()));
+            assert_eq!(ink_voting_dapp.get_voter_weigth(1, accounts.bob), u128::MAX);
+        }
+        #[ink::test]
+        fn delegate_vote_delegated_overflow_fails() {
+            let mut ink_voting_dapp = initialize_and_create_election(false).unwrap();
+            ink_voting_dapp.open_election(1).unwrap();
+            let accounts = ink_env::test::default_accounts::<ink_env::DefaultEnvironment>();
+            ink_voting_dapp
+                .voters
+                .insert((1, accounts.bob), &(0, false));
+            ink_voting_dapp
+                .delegated_in
+                .insert((1, accounts.bob), &u128::MAX);
+            assert_eq!(
+                ink_voting_dapp.delegate_vote(1, accounts.bob, 1),
+                Err(Error::WeightOverflow)
+            );
+            assert_eq!(ink_voting_dapp.get_voter_weigth(1, accounts.bob), 0);
+            assert_eq!(ink_voting_dapp.get_voter_weigth(1, accounts.alice), 1);
+        }
+        #[ink::test]
+        fn delegate_vote_to_self_fails() {
+            let mut ink_voting_dapp = initialize_and_create_election(false).unwrap();
+            ink_voting_dapp.open_election(1).unwrap();
+            let alice = ink_env::test::default_accounts::<ink_env::DefaultEnvironment>().alice;
+            assert_eq!(
+                ink_voting_dapp.delegate_vote(1, alice, 1),
+                Err(Error::SelfDelegation)
+            );
+            assert_eq!(ink_voting_dapp.get_delegated_weight(1, alice), 0);
+            assert!(!ink_voting_dapp.is_account_registered(1, alice));
+        }
+        #[ink::test]
         fn delegate_vote_with_registration_works() {
             let mut ink_voting_dapp = initialize_and_create_election(true).unwrap();
             let bob = ink_env::test::default_accounts::<ink_env::DefaultEnvironment>().bob;
@@ -873,13 +6734,18 @@ mod ink_voting_dapp {
             );
             assert_eq!(
                 ink_voting_dapp.delegate_vote(1, bob, 1),
-                Err(Error::VoterNotRegistred)
+                Err(Error::ElectionClosed)
+            );
+            ink_voting_dapp.open_election(1).unwrap();
+            assert_eq!(
+                ink_voting_dapp.delegate_vote(1, bob, 1),
+                Err(Error::DelegatorNotRegistered)
             );
             ink_voting_dapp.open_registration(1).unwrap();
             ink_voting_dapp.register_me(1).unwrap();
             assert_eq!(
                 ink_voting_dapp.delegate_vote(1, bob, 1),
-                Err(Error::VoterNotRegistred)
+                Err(Error::DelegateNotRegistered)
             );
             ink_voting_dapp.register(1, bob).unwrap();
             assert_eq!(
@@ -891,7 +6757,7 @@ mod ink_voting_dapp {
             assert_eq!(ink_voting_dapp.get_voter_weigth(1, alice), 0);
             assert_eq!(ink_voting_dapp.has_voter_voted(1, bob), false);
             assert_eq!(ink_voting_dapp.get_voter_weigth(1, bob), 2);
-            assert_eq!(ink_env::test::recorded_events().count(), 5);
+            assert_eq!(ink_env::test::recorded_events().count(), 6);
             assert_eq!(
                 ink_voting_dapp.delegate_vote(1, bob, 1),
                 Err(Error::VoterHasAlreadyVoted)